@@ -0,0 +1,125 @@
+//! Idle CPU of the dial's clock, for comparing the scheduler against the old
+//! 16 ms poll (see "Idle Cost" in the README).
+//!
+//! ```text
+//! cargo run --release --no-default-features --example clock_cpu -- old 120
+//! cargo run --release --no-default-features --example clock_cpu -- new 120
+//! cargo run --release --no-default-features --example clock_cpu -- occluded 120
+//! cargo run --release --no-default-features --example clock_cpu -- app <pid> 120
+//! ```
+//!
+//! `old`, `new` and `occluded` replay the clock's own loop in this process,
+//! without a window: what the Rust side costs and how often it wakes. `app`
+//! samples a running `chronos-aeternum` and every process below it (the
+//! webview's renderer included), which is the figure that includes painting.
+//! `app` reads `/proc`, so it works on Linux only.
+
+use chrono::{Local, Timelike};
+use chronos_aeternum::geometry::{self, DialLayout};
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// User plus system CPU time this process has used.
+fn own_cpu() -> Duration {
+    // SAFETY: getrusage only writes into the zeroed struct it is handed
+    let usage = unsafe {
+        let mut usage: libc::rusage = std::mem::zeroed();
+        libc::getrusage(libc::RUSAGE_SELF, &mut usage);
+        usage
+    };
+    let time = |tv: libc::timeval| Duration::new(tv.tv_sec as u64, tv.tv_usec as u32 * 1000);
+    time(usage.ru_utime) + time(usage.ru_stime)
+}
+
+/// User plus system CPU time used by `root` and all its descendants.
+fn tree_cpu(root: u32) -> Duration {
+    // (pid, parent, clock ticks) of every process
+    let mut procs = Vec::new();
+    for entry in fs::read_dir("/proc").into_iter().flatten().flatten() {
+        let Ok(pid) = entry.file_name().to_string_lossy().parse::<u32>() else { continue };
+        let Ok(stat) = fs::read_to_string(entry.path().join("stat")) else { continue };
+        // The command name may hold spaces, so fields are counted after its closing parenthesis
+        let Some((_, rest)) = stat.rsplit_once(')') else { continue };
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        let field = |n: usize| fields.get(n).and_then(|f| f.parse::<u64>().ok()).unwrap_or(0);
+        procs.push((pid, field(1) as u32, field(11) + field(12)));
+    }
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i];
+        tree.extend(procs.iter().filter(|(_, ppid, _)| *ppid == parent).map(|(pid, _, _)| *pid));
+        i += 1;
+    }
+    // SAFETY: sysconf has no preconditions
+    let hz = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as u64;
+    let ticks: u64 = procs.iter().filter(|(pid, _, _)| tree.contains(pid)).map(|(_, _, ticks)| ticks).sum();
+    Duration::from_millis(ticks * 1000 / hz)
+}
+
+fn until_next_second() -> Duration {
+    Duration::from_nanos(1_000_000_000 - u64::from(Local::now().nanosecond() % 1_000_000_000))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let arg = |n: usize| args.get(n).and_then(|s| s.parse::<u64>().ok());
+    let mode = args.first().map(String::as_str).unwrap_or_default();
+    let (secs, target) = match mode {
+        "app" => (arg(2).unwrap_or(60), Some(arg(1).expect("app needs the dial's process id") as u32)),
+        _ => (arg(1).unwrap_or(60), None),
+    };
+    let cpu = || target.map_or_else(own_cpu, tree_cpu);
+    let end = Instant::now() + Duration::from_secs(secs);
+    let (cpu_before, started) = (cpu(), Instant::now());
+    let mut wakeups = 0u64;
+
+    match mode {
+        // Before: every 16 ms, read the clock and lay out the whole dial
+        "old" => {
+            while Instant::now() < end {
+                black_box(DialLayout::at(&Local::now()));
+                wakeups += 1;
+                thread::sleep(Duration::from_millis(16));
+            }
+        }
+        // Visible or minimized: a tick on every whole second, the counter each
+        // tick and the face only when the minute changes
+        "new" => {
+            let mut minute = None;
+            while Instant::now() < end {
+                let now = Local::now();
+                black_box(geometry::day_elapsed(&now));
+                if minute != Some(now.minute()) {
+                    minute = Some(now.minute());
+                    black_box(DialLayout::at(&now));
+                }
+                wakeups += 1;
+                thread::sleep(until_next_second());
+            }
+        }
+        // Occluded: parked until something wakes it, which here nothing does
+        "occluded" => {
+            let (_wake, parked) = mpsc::channel::<()>();
+            let _ = parked.recv_timeout(end - Instant::now());
+        }
+        // A running dial: only watch it
+        "app" => thread::sleep(end - Instant::now()),
+        _ => {
+            eprintln!("usage: clock_cpu old|new|occluded [SECONDS] | app PID [SECONDS]");
+            std::process::exit(2);
+        }
+    }
+
+    let (used, wall) = (cpu() - cpu_before, started.elapsed());
+    let share = used.as_secs_f64() / wall.as_secs_f64() * 100.0;
+    print!("{mode:9} wall {:6.1} s  cpu {:9.2} ms  {share:.4}% of one core", wall.as_secs_f64(), used.as_secs_f64() * 1e3);
+    if target.is_none() {
+        print!("  wakeups {wakeups}");
+    }
+    println!();
+}
@@ -0,0 +1,193 @@
+use chronos_aeternum::geometry;
+use chrono::{DateTime, Local, Timelike};
+use dioxus::desktop::tao::event::{Event, WindowEvent};
+use dioxus::desktop::use_wry_event_handler;
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+// --- Frame Scheduling ---
+
+/// How much of the window can be seen, which sets how often the clock runs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Presence {
+    /// On screen: the hands sweep by CSS animation, the rest follows each second.
+    #[default]
+    Visible,
+    /// Minimized: still one update a second, but the hand animations stop.
+    Minimized,
+    /// Covered or hidden: nothing runs until the window is seen again.
+    Occluded,
+}
+
+impl Presence {
+    fn ticks(self) -> bool {
+        self != Presence::Occluded
+    }
+}
+
+/// Reports the page being hidden or shown. The window system does not tell
+/// tao about occlusion, but every webview does tell its page.
+const VISIBILITY_JS: &str = r#"
+document.addEventListener('visibilitychange', () => dioxus.send(document.hidden));
+"#;
+
+/// The wall clock as the dial sees it.
+#[derive(Clone, Copy)]
+pub struct Clock {
+    /// Current time, at most once a second; read it only in small components.
+    pub now: Signal<DateTime<Local>>,
+    pub presence: Signal<Presence>,
+    /// Bumped each minute and whenever the hands must be put back in step;
+    /// the dial face redraws when it changes.
+    pub epoch: Signal<u64>,
+    occluded: Signal<bool>,
+}
+
+impl Clock {
+    /// Advances `now`, starting a new epoch when the minute changes (or the
+    /// system clock jumped).
+    fn tick(mut self) {
+        let now = Local::now();
+        let last = *self.now.peek();
+        self.now.set(now);
+        if (now.date_naive(), now.hour(), now.minute()) != (last.date_naive(), last.hour(), last.minute()) {
+            self.epoch += 1;
+        }
+    }
+
+    /// Re-reads the window's state after an event that may have changed it.
+    fn refresh(mut self, wake: &Notify) {
+        let presence = if *self.occluded.peek() {
+            Presence::Occluded
+        } else if dioxus::desktop::window().is_minimized() {
+            Presence::Minimized
+        } else {
+            Presence::Visible
+        };
+        if *self.presence.peek() != presence {
+            self.presence.set(presence);
+            self.epoch += 1;
+            wake.notify_one();
+        }
+    }
+}
+
+/// Runs the clock: an update on every whole second while the window can be
+/// seen, none while it is occluded.
+pub fn use_clock() -> Clock {
+    let clock = Clock {
+        now: use_signal(Local::now),
+        presence: use_signal(Presence::default),
+        epoch: use_signal(|| 0),
+        occluded: use_signal(|| false),
+    };
+    let wake = use_hook(|| Rc::new(Notify::new()));
+
+    // Minimizing resizes the window and usually takes focus away
+    use_wry_event_handler({
+        let wake = wake.clone();
+        move |event, _| {
+            if let Event::WindowEvent { event: WindowEvent::Resized(_) | WindowEvent::Focused(_), .. } = event {
+                clock.refresh(&wake);
+            }
+        }
+    });
+
+    use_future({
+        let wake = wake.clone();
+        move || {
+            let wake = wake.clone();
+            async move {
+                let mut hidden = document::eval(VISIBILITY_JS);
+                let mut occluded = clock.occluded;
+                while let Ok(is_hidden) = hidden.recv::<bool>().await {
+                    occluded.set(is_hidden);
+                    clock.refresh(&wake);
+                }
+            }
+        }
+    });
+
+    use_future(move || {
+        let wake = wake.clone();
+        async move {
+            loop {
+                if clock.presence.peek().ticks() {
+                    clock.tick();
+                    tokio::select! {
+                        _ = tokio::time::sleep(until_next_second(&Local::now())) => {}
+                        _ = wake.notified() => {}
+                    }
+                } else {
+                    wake.notified().await;
+                }
+            }
+        }
+    });
+
+    clock
+}
+
+/// Time left until `now` reaches the next whole second.
+fn until_next_second(now: &DateTime<Local>) -> Duration {
+    Duration::from_nanos(1_000_000_000 - u64::from(now.nanosecond() % 1_000_000_000))
+}
+
+/// Seconds into the current twelve-hour turn of the dial.
+fn turn_seconds(now: &DateTime<Local>) -> f64 {
+    let wall = now.time();
+    (wall.num_seconds_from_midnight() % 43_200) as f64 + wall.nanosecond().min(999_999_999) as f64 / 1e9
+}
+
+/// Style turning a hand once per `period` seconds, started `phase` seconds in.
+/// The keyframe name alternates with `epoch` so a new epoch restarts the
+/// animation instead of shifting the running one.
+fn turning(epoch: u64, period: f64, phase: f64, presence: Presence) -> String {
+    let name = if epoch.is_multiple_of(2) { "hand-turn-even" } else { "hand-turn-odd" };
+    let state = if presence == Presence::Visible { "running" } else { "paused" };
+    format!("transform-origin: 400px 400px; animation: {name} {period}s linear -{phase:.3}s infinite; animation-play-state: {state};")
+}
+
+/// The three hands and the Spirit Dot, set in place once per epoch and
+/// turned by the webview from there.
+#[component]
+pub fn DialHands(epoch: u64, presence: Presence) -> Element {
+    let turn = turn_seconds(&Local::now());
+    let hour = turning(epoch, 43_200.0, turn, presence);
+    let minute = turning(epoch, 3_600.0, turn % 3_600.0, presence);
+    let second = turning(epoch, 60.0, turn % 60.0, presence);
+    let dot_y = geometry::CENTER - geometry::SPIRIT_RADIUS;
+
+    rsx! {
+        // The "Spirit Dot" Minute Indicator
+        g {
+            style: "{minute}",
+            circle { cx: "400", cy: "{dot_y}", r: "4", fill: "#FCF6BA", filter: "url(#luxuryGlow)" }
+        }
+        // Hands Layer
+        g {
+            style: "{hour}",
+            line { x1: "400", y1: "400", x2: "400", y2: "295", stroke: "url(#goldGradient)", stroke_width: "14", stroke_linecap: "round", filter: "url(#handShadow)" }
+        }
+        g {
+            style: "{minute}",
+            line { x1: "400", y1: "400", x2: "400", y2: "215", stroke: "#FCF6BA", stroke_width: "6", stroke_linecap: "round", filter: "url(#handShadow)" }
+        }
+        g {
+            style: "{second}",
+            line { x1: "400", y1: "430", x2: "400", y2: "190", stroke: "#AA771C", stroke_width: "2" }
+            circle { cx: "400", cy: "190", r: "6", fill: "#FCF6BA", filter: "url(#luxuryGlow)" }
+        }
+    }
+}
+
+/// Seconds lived today, the one figure that changes every second.
+#[component]
+pub fn ExperienceCounter(now: ReadOnlySignal<DateTime<Local>>) -> Element {
+    let (lived, _) = geometry::day_elapsed(&now());
+    rsx! {
+        div { class: "gold-text", style: "font-size: 2.5rem; font-weight: 900; font-family: var(--font-display);", "{lived}" }
+    }
+}
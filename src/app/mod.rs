@@ -0,0 +1,1231 @@
+#![allow(non_snake_case)]
+use chronos_aeternum::config::Fonts;
+use chronos_aeternum::geometry::{self, DialLayout, TickKind};
+use chronos_aeternum::integrity::IntegrityReport;
+use chronos_aeternum::markdown::{render_note, toggle_task, WikiLinks, THEME_CSS};
+use chronos_aeternum::note::{coverage_level, HourState, NoteKey};
+use chronos_aeternum::search::Query;
+use chrono::{Datelike, Local, Months, NaiveDate, Timelike};
+use dioxus::desktop::tao::event::{Event, WindowEvent};
+use dioxus::desktop::use_wry_event_handler;
+use dioxus::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+mod clock;
+mod vault;
+
+use clock::{use_clock, DialHands, ExperienceCounter};
+use vault::{bank, edit, secure, Vault};
+
+// --- Calendar ---
+
+/// Hours with an observation on each day of the month starting at `first`,
+/// as (written, banked).
+fn month_density(vault: &Vault, first: NaiveDate) -> BTreeMap<NaiveDate, (usize, usize)> {
+    let last = first.checked_add_months(Months::new(1)).and_then(|d| d.pred_opt()).unwrap_or(first);
+    let mut density = BTreeMap::new();
+    for (key, note) in vault.range(Some(first), Some(last)) {
+        let (written, banked) = density.entry(key.date).or_insert((0, 0));
+        *written += 1;
+        *banked += usize::from(note.is_locked);
+    }
+    density
+}
+
+// --- Search ---
+
+/// Most results the search overlay lists.
+const SEARCH_RESULTS: usize = 50;
+
+/// What the search overlay is asking for. The date pickers take precedence
+/// over any `from:`/`to:` typed into the query.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct SearchPanel {
+    text: String,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+}
+
+impl SearchPanel {
+    fn query(&self) -> Query {
+        let mut query = Query::parse(&self.text);
+        query.from = self.from.or(query.from);
+        query.to = self.to.or(query.to);
+        query
+    }
+}
+
+// --- Preview ---
+
+/// Reports clicks on the preview's wiki-links, and on its task boxes while
+/// the note is editable.
+const PREVIEW_CLICKS_JS: &str = r#"
+document.addEventListener('click', event => {
+    const link = event.target.closest('.markdown-body a.wiki-link[data-key]');
+    if (link) {
+        event.preventDefault();
+        dioxus.send({ Open: link.dataset.key });
+        return;
+    }
+    const box = event.target.closest('.tasks-live .task-box');
+    if (box) dioxus.send({ Task: Number(box.dataset.task) });
+});
+"#;
+
+/// A click reported by `PREVIEW_CLICKS_JS`.
+#[derive(Deserialize)]
+enum PreviewClick {
+    /// A task box, by its `data-task` index.
+    Task(usize),
+    /// A wiki-link to the hour with this key.
+    Open(String),
+}
+
+/// Turns the dial to the day and half of `key` and opens its node.
+fn open_node(key: NoteKey, mut viewed_date: Signal<Option<NaiveDate>>, mut dial_pm: Signal<bool>, mut selected: Signal<Option<NoteKey>>) {
+    viewed_date.set((key.date != Local::now().date_naive()).then_some(key.date));
+    dial_pm.set(key.hour >= 12);
+    selected.set(Some(key));
+}
+
+// --- Fonts ---
+
+/// Cinzel and Montserrat, shipped with the app; see `assets/fonts/README.md`.
+const FONT_DIR: Asset = asset!("/assets/fonts");
+
+/// Declares the bundled faces, or points the theme's font variables at
+/// system families instead.
+fn font_css(fonts: Fonts) -> String {
+    match fonts {
+        Fonts::Bundled => format!(
+            "@font-face {{ font-family: 'Cinzel'; src: url('{FONT_DIR}/Cinzel[wght].ttf') format('truetype'); font-weight: 400 900; font-display: swap; }}\n\
+             @font-face {{ font-family: 'Montserrat'; src: url('{FONT_DIR}/Montserrat[wght].ttf') format('truetype'); font-weight: 100 900; font-display: swap; }}"
+        ),
+        Fonts::System => ":root { --font-display: Georgia, 'Times New Roman', serif; --font-body: system-ui, -apple-system, 'Segoe UI', sans-serif; }".into(),
+    }
+}
+
+// --- Styles (The "Luxury Gold" Theme) ---
+
+/// Opens the dial in a desktop window.
+pub fn launch() {
+    let cfg = dioxus::desktop::Config::default()
+        .with_window(dioxus::desktop::WindowBuilder::new().with_title("Chronos Aeternum Plantacerium"));
+    LaunchBuilder::desktop().with_cfg(cfg).launch(App);
+}
+
+fn App() -> Element {
+    // Wall clock, advanced once a second while the window can be seen
+    let clock = use_clock();
+    // The archive: Date-Hour (YYYY-MM-DD-HH) keys to Notes, on the configured backend
+    let mut vault = use_signal(Vault::open);
+    // State for the Date-Hour currently open in the observation modal
+    let mut selected = use_signal(|| None::<NoteKey>);
+    // Day shown on the dial; `None` follows today, even across midnight
+    let mut viewed_date = use_signal(|| None::<NaiveDate>);
+    // Month shown in the calendar overlay (its first day); `None` when closed
+    let mut calendar_month = use_signal(|| None::<NaiveDate>);
+    // Search overlay state; `None` when closed
+    let mut search = use_signal(|| None::<SearchPanel>);
+    // Which half of the day the twelve markers address (starts on the current one)
+    let mut dial_pm = use_signal(|| Local::now().hour() >= 12);
+
+    // Last chance to write unsaved edits before the window goes away
+    use_wry_event_handler(move |event, _| {
+        if let Event::WindowEvent { event: WindowEvent::CloseRequested, .. } = event {
+            vault.write().flush_now();
+        }
+    });
+
+    // Bank planned hours as they elapse: the whole archive on launch, then recent days each minute
+    use_future(move || async move {
+        let mut since = None;
+        loop {
+            let enabled = {
+                let vault = vault.peek();
+                vault.config.auto_bank && !vault.volatile
+            };
+            if enabled {
+                let now = Local::now().naive_local();
+                let due = vault.peek().due_for_banking(since, now);
+                bank(vault, due).await;
+                since = now.date().pred_opt();
+            }
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+
+    // Task boxes flip the matching `- [ ]` in the open note; wiki-links open their hour
+    use_future(move || async move {
+        let mut clicks = document::eval(PREVIEW_CLICKS_JS);
+        while let Ok(click) = clicks.recv::<PreviewClick>().await {
+            match click {
+                PreviewClick::Task(task) => {
+                    let Some(key) = *selected.peek() else { continue };
+                    let Some(note) = vault.peek().get(key).filter(|note| !note.is_locked) else { continue };
+                    if let Some(content) = toggle_task(&note.content, task) {
+                        edit(vault, key, content);
+                    }
+                }
+                PreviewClick::Open(key) => {
+                    if let Some(key) = NoteKey::parse(&key) {
+                        open_node(key, viewed_date, dial_pm, selected);
+                    }
+                }
+            }
+        }
+    });
+
+    // The face redraws once a minute; the hands and the counter move on their own
+    let epoch = *clock.epoch.read();
+    let presence = *clock.presence.read();
+    let t = *clock.now.peek();
+    let today = t.date_naive();
+    let dial_date = viewed_date().unwrap_or(today);
+    let day_notes = vault.read().day(dial_date);
+    let layout = DialLayout::at(&t);
+    let stroke_dasharray = layout.day_arc;
+
+    // Latest "Secure State" integrity report, shown until dismissed (or briefly, if clean)
+    let mut integrity = use_signal(|| None::<IntegrityReport>);
+    let display_modal_h = selected()
+        .map(|key| {
+            let (h, meridiem) = geometry::twelve_hour(key.hour);
+            format!("{h} {meridiem}")
+        })
+        .unwrap_or_default();
+    let dial_date_label = dial_date.format("%a %d %b %Y").to_string();
+    let dial_date_value = dial_date.format("%Y-%m-%d").to_string();
+    let meridiem_label = if dial_pm() { "Post Meridiem" } else { "Ante Meridiem" };
+
+    // Secure, verify and report
+    let on_save = move |_| {
+        spawn(async move {
+            let Some(report) = secure(vault).await else { return };
+            let passed = report.passed();
+            integrity.set(Some(report.clone()));
+            // A clean report fades after a few seconds; problems stay until dismissed
+            if passed {
+                tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+                if integrity.peek().as_ref() == Some(&report) {
+                    integrity.set(None);
+                }
+            }
+        });
+    };
+
+    rsx! {
+        // Embed Critical CSS for guaranteed luxury rendering
+        style { {THEME_CSS} }
+        style { {font_css(vault.read().config.fonts)} }
+        style {
+            "
+            body {{ 
+                margin: 0; padding: 0; background: #020202; 
+                color: #FCF6BA; font-family: var(--font-body);
+                overflow: hidden;
+            }}
+
+            .viewport-center {{
+                width: 100vw; height: 100vh;
+                position: relative;
+                display: flex; flex-direction: column; justify-content: center; align-items: center;
+                background: radial-gradient(circle at center, #0a0a0a 0%, #000 100%);
+            }}
+
+            .mandala-layer {{
+                position: absolute;
+                top: 50%; left: 50%;
+                transform: translate(-50%, -50%);
+                z-index: 1;
+                opacity: 0.8;
+                animation: rotate-mandala-ccw 600s linear infinite;
+                pointer-events: none;
+            }}
+
+            @keyframes rotate-mandala-ccw {{
+                from {{ transform: translate(-50%, -50%) rotate(360deg); }}
+                to {{ transform: translate(-50%, -50%) rotate(0deg); }}
+            }}
+
+            .watch-layer {{
+                position: relative;
+                z-index: 10;
+                width: 850px; height: 850px;
+                display: flex; justify-content: center; align-items: center;
+                margin-top: 20px;
+            }}
+
+            @keyframes breathe-glow {{
+                0% {{ opacity: 0.4; stroke-width: 8; }}
+                50% {{ opacity: 0.8; stroke-width: 15; }}
+                100% {{ opacity: 0.4; stroke-width: 8; }}
+            }}
+
+            .rim-glow-breathe {{
+                animation: breathe-glow 8s infinite ease-in-out;
+            }}
+
+            .luxury-btn {{
+                background: linear-gradient(135deg, #AA771C, #FCF6BA, #AA771C);
+                background-size: 200% 200%;
+                border: none;
+                padding: 12px 30px;
+                font-family: var(--font-display);
+                font-weight: 900;
+                letter-spacing: 3px;
+                cursor: pointer;
+                transition: all 0.4s cubic-bezier(0.175, 0.885, 0.32, 1.275);
+                color: #000;
+                border-radius: 24px;
+                box-shadow: 0 0 15px rgba(212, 175, 55, 0.15);
+                text-transform: uppercase;
+            }}
+
+            .luxury-btn:hover {{
+                background-position: 100% 0;
+                transform: scale(1.05);
+                box-shadow: 0 0 40px rgba(212, 175, 55, 0.4);
+            }}
+
+            /* Two identical turns, so switching between them restarts a hand in step */
+            @keyframes hand-turn-even {{
+                from {{ transform: rotate(0deg); }}
+                to {{ transform: rotate(360deg); }}
+            }}
+            @keyframes hand-turn-odd {{
+                from {{ transform: rotate(0deg); }}
+                to {{ transform: rotate(360deg); }}
+            }}
+
+            @keyframes pulse-hub {{
+                0% {{ transform: scale(0.92); opacity: 0.8; }}
+                50% {{ transform: scale(1.08); opacity: 1; }}
+                100% {{ transform: scale(0.92); opacity: 0.8; }}
+            }}
+
+            /* Dual Direction Emanation */
+            .emanate-out {{
+                position: absolute;
+                top: 50%; left: 50%;
+                transform: translate(-50%, -50%);
+                border-radius: 50%;
+                border: 1px solid rgba(212, 175, 55, 0.06);
+                animation: emanate-out 8s infinite ease-out;
+                pointer-events: none;
+            }}
+
+            .emanate-in {{
+                position: absolute;
+                top: 50%; left: 50%;
+                transform: translate(-50%, -50%);
+                border-radius: 50%;
+                border: 1px solid rgba(212, 175, 55, 0.04);
+                animation: emanate-in 10s infinite ease-in-out;
+                pointer-events: none;
+            }}
+
+            @keyframes emanate-out {{
+                0% {{ width: 100px; height: 100px; opacity: 0; transform: translate(-50%, -50%) scale(0.6); }}
+                40% {{ opacity: 0.2; }}
+                100% {{ width: 1400px; height: 1400px; opacity: 0; transform: translate(-50%, -50%) scale(1.1); }}
+            }}
+
+            @keyframes emanate-in {{
+                0% {{ width: 1600px; height: 1600px; opacity: 0; transform: translate(-50%, -50%) scale(1.2); }}
+                50% {{ opacity: 0.15; }}
+                100% {{ width: 200px; height: 200px; opacity: 0; transform: translate(-50%, -50%) scale(0.5); }}
+            }}
+
+            .save-status {{
+                position: fixed;
+                top: 30px; right: 30px;
+                padding: 18px 35px;
+                background: rgba(10, 10, 10, 0.95);
+                border: 1px solid var(--gold-dark);
+                color: var(--gold-light);
+                font-family: var(--font-display);
+                font-weight: 700;
+                letter-spacing: 2px;
+                z-index: 1000;
+                border-radius: 4px;
+                box-shadow: 0 10px 40px rgba(0,0,0,0.8);
+                animation: slide-in 0.6s cubic-bezier(0.23, 1, 0.32, 1);
+            }}
+
+            .integrity-detail {{
+                margin-top: 8px;
+                font-family: var(--font-body);
+                font-size: 0.7rem; font-weight: 400;
+                letter-spacing: 2px;
+                color: #888;
+            }}
+
+            .integrity-failed {{
+                border-color: #8B2500;
+                max-width: 40vw;
+            }}
+
+            .integrity-problem {{
+                margin-top: 6px;
+                font-family: var(--font-body);
+                font-size: 0.7rem; font-weight: 400;
+                letter-spacing: 1px;
+                color: #F5C6A5;
+            }}
+
+            .nav-btn {{
+                background: transparent;
+                border: 1px solid rgba(212, 175, 55, 0.3);
+                color: #D4AF37;
+                font-family: var(--font-display);
+                font-size: 1.1rem;
+                padding: 2px 12px;
+                border-radius: 12px;
+                cursor: pointer;
+                transition: all 0.3s ease;
+            }}
+
+            .nav-btn:hover:not(:disabled) {{
+                border-color: #FCF6BA;
+                box-shadow: 0 0 15px rgba(212, 175, 55, 0.3);
+            }}
+
+            .nav-btn:disabled {{
+                opacity: 0.25;
+                cursor: default;
+            }}
+
+            .nav-date {{
+                background: #000;
+                color: #FCF6BA;
+                border: 1px solid rgba(212, 175, 55, 0.2);
+                padding: 6px 10px;
+                font-family: var(--font-body);
+                color-scheme: dark;
+            }}
+
+            .calendar-grid {{
+                display: grid;
+                grid-template-columns: repeat(7, 1fr);
+                gap: 6px;
+            }}
+
+            .calendar-weekday {{
+                text-align: center;
+                font-size: 0.65rem;
+                color: #555;
+                letter-spacing: 3px;
+                text-transform: uppercase;
+                padding-bottom: 6px;
+            }}
+
+            .calendar-day {{
+                height: 62px;
+                padding: 8px 10px;
+                border: 1px solid rgba(212, 175, 55, 0.08);
+                color: #888;
+                font-family: var(--font-display);
+                font-weight: 700;
+                cursor: pointer;
+                display: flex;
+                flex-direction: column;
+                justify-content: space-between;
+                transition: all 0.3s ease;
+            }}
+
+            .calendar-day:hover {{
+                border-color: #FCF6BA;
+                box-shadow: 0 0 15px rgba(212, 175, 55, 0.3);
+            }}
+
+            .calendar-today {{
+                border-color: #D4AF37;
+            }}
+
+            .calendar-count {{
+                align-self: flex-end;
+                font-family: var(--font-body);
+                font-size: 0.65rem;
+                letter-spacing: 1px;
+            }}
+
+            .calendar-swatch {{
+                width: 14px;
+                height: 14px;
+                border: 1px solid rgba(212, 175, 55, 0.08);
+            }}
+
+            .heat-0 {{ background: #050505; }}
+            .heat-1 {{ background: rgba(212, 175, 55, 0.12); color: #AA771C; }}
+            .heat-2 {{ background: rgba(212, 175, 55, 0.28); color: #D4AF37; }}
+            .heat-3 {{ background: rgba(212, 175, 55, 0.5); color: #FCF6BA; }}
+            .heat-4 {{ background: linear-gradient(135deg, #BF953F, #FCF6BA, #B38728); color: #000; }}
+
+            .search-input {{
+                background: #000;
+                border: 1px solid rgba(212, 175, 55, 0.25);
+                color: #FCF6BA;
+                padding: 18px 24px;
+                font-family: var(--font-body);
+                font-size: 1.1rem;
+                letter-spacing: 1px;
+                outline: none;
+            }}
+
+            .search-input:focus {{
+                border-color: #D4AF37;
+            }}
+
+            .search-hit {{
+                padding: 14px 18px;
+                border-left: 2px solid transparent;
+                cursor: pointer;
+                transition: all 0.3s ease;
+            }}
+
+            .search-hit:hover {{
+                background: rgba(212, 175, 55, 0.05);
+                border-left-color: #D4AF37;
+            }}
+
+            .search-hit-key {{
+                font-family: var(--font-display);
+                font-weight: 700;
+                font-size: 0.75rem;
+                letter-spacing: 4px;
+                color: #AA771C;
+                text-transform: uppercase;
+                margin-bottom: 6px;
+            }}
+
+            .search-hit-snippet {{
+                color: #999;
+                font-size: 0.9rem;
+                line-height: 1.6;
+            }}
+
+            .search-hit-snippet mark {{
+                background: rgba(212, 175, 55, 0.2);
+                color: #FCF6BA;
+            }}
+
+            .backlinks {{
+                display: flex;
+                flex-wrap: wrap;
+                align-items: baseline;
+                gap: 10px 18px;
+            }}
+
+            .backlinks-title {{
+                font-family: var(--font-display);
+                font-size: 0.7rem;
+                letter-spacing: 6px;
+                color: #444;
+                text-transform: uppercase;
+            }}
+
+            .backlink {{
+                font-family: var(--font-display);
+                font-size: 0.75rem;
+                letter-spacing: 3px;
+                color: #AA771C;
+                border-bottom: 1px solid rgba(212, 175, 55, 0.2);
+                cursor: pointer;
+                transition: all 0.3s ease;
+            }}
+
+            .backlink:hover {{
+                color: #FCF6BA;
+                border-bottom-color: #D4AF37;
+            }}
+
+            .vault-error {{
+                right: auto; left: 30px;
+                max-width: 40vw;
+                border-color: #8B2500;
+                color: #F5C6A5;
+                letter-spacing: 1px;
+            }}
+
+            @keyframes slide-in {{
+                from {{ transform: translateX(120%); opacity: 0; }}
+                to {{ transform: translateX(0); opacity: 1; }}
+            }}
+            "
+        }
+
+        div { class: "viewport-center",
+            
+            // 1. Counter-Clockwise Mandala
+            div { class: "mandala-layer",
+                svg {
+                    view_box: "0 0 1000 1000",
+                    style: "width: 1300px; height: 1300px;",
+                    defs {
+                        linearGradient { id: "mandalaGold", x1: "0%", y1: "0%", x2: "100%", y2: "100%",
+                            stop { offset: "0%", stop_color: "#BF953F", stop_opacity: "0.4" }
+                            stop { offset: "100%", stop_color: "#AA771C", stop_opacity: "0.05" }
+                        }
+                    }
+                    // Mandala Lotus Geometry
+                    for i in 0..32 {
+                        {
+                            let rotation = i as f64 * (360.0 / 32.0);
+                            rsx! {
+                                path {
+                                    d: "M 500 500 C 520 400 580 400 600 500 C 580 600 520 600 500 500",
+                                    fill: "none",
+                                    stroke: "url(#mandalaGold)",
+                                    stroke_width: "0.4",
+                                    style: "transform-origin: 500px 500px; transform: rotate({rotation}deg) translate(0, -320px) scale(2.5, 1.5);"
+                                }
+                            }
+                        }
+                    }
+                    circle { cx: "500", cy: "500", r: "485", fill: "none", stroke: "url(#mandalaGold)", stroke_width: "0.5", opacity: "0.15" }
+                }
+            }
+
+            // 2. Centered Chronometer & Dual Emanations
+            div { class: "watch-layer",
+                
+                // Outward rings
+                for i in 0..3 {
+                    {
+                        let delay = i as f64 * 2.5;
+                        rsx! { div { class: "emanate-out", style: "animation-delay: {delay}s;" } }
+                    }
+                }
+                // Inward rings
+                for i in 0..2 {
+                    {
+                        let delay = i as f64 * 5.0;
+                        rsx! { div { class: "emanate-in", style: "animation-delay: {delay}s;" } }
+                    }
+                }
+
+                svg {
+                    view_box: "0 0 800 800",
+                    style: "width: 100%; height: 100%; overflow: visible; filter: drop-shadow(0 0 100px rgba(0,0,0,0.95)); position: relative; z-index: 20;",
+
+                    defs {
+                        linearGradient { id: "goldGradient", x1: "0%", y1: "0%", x2: "100%", y2: "100%",
+                            stop { offset: "0%", stop_color: "#BF953F" }
+                            stop { offset: "50%", stop_color: "#FCF6BA" }
+                            stop { offset: "100%", stop_color: "#AA771C" }
+                        }
+                        radialGradient { id: "dialGradient", cx: "50%", cy: "50%", r: "50%",
+                            stop { offset: "0%", stop_color: "#1a1a1a" }
+                            stop { offset: "80%", stop_color: "#080808" }
+                            stop { offset: "100%", stop_color: "#000" }
+                        }
+                        // Universal Safe-Zone Glow Filters (Prevent ViewBox Clipping)
+                        filter { id: "innerGlow", filterUnits: "userSpaceOnUse", x: "0", y: "0", width: "800", height: "800",
+                            feGaussianBlur { std_deviation: "20", _in: "SourceAlpha", result: "blur" }
+                            feOffset { dx: "0", dy: "0" }
+                            feComposite { _in: "SourceAlpha", in2: "blur", operator: "arithmetic", k2: "-1", k3: "1" }
+                            feColorMatrix { type: "matrix", values: "0 0 0 0 0.83  0 0 0 0 0.68  0 0 0 0 0.21  0 0 0 0 0.6 0" }
+                        }
+                        filter { id: "luxuryGlow", filterUnits: "userSpaceOnUse", x: "-100", y: "-100", width: "1000", height: "1000",
+                            feGaussianBlur { std_deviation: "22", result: "blur" }
+                            feColorMatrix { type: "matrix", values: "1 0 0 0 0  0 1 0 0 0  0 0 1 0 0  0 0 0 1.5 0" }
+                            feComposite { _in: "SourceGraphic", in2: "blur", operator: "over" }
+                        }
+                        filter { id: "handShadow",
+                            feDropShadow { dx: "5", dy: "5", std_deviation: "4", flood_opacity: "0.8" }
+                        }
+                    }
+
+                    // Watch Face Base with Deep Inner Glow (Non-Clipped)
+                    circle { cx: "400", cy: "400", r: "248", fill: "none", stroke: "url(#goldGradient)", stroke_width: "3" }
+                    circle { cx: "400", cy: "400", r: "245", fill: "url(#dialGradient)", stroke: "rgba(255,255,255,0.05)", stroke_width: "1" }
+                    circle { cx: "400", cy: "400", r: "245", fill: "none", filter: "url(#innerGlow)" }
+
+                    // Tick Marks (Temporal Wake Implementation)
+                    for tick in layout.ticks.iter() {
+                        {
+                            let is_five = tick.kind != TickKind::Minute;
+                            let is_primary = tick.kind == TickKind::Primary;
+                            let has_passed = tick.passed;
+                            let ((x1, y1), (x2, y2)) = (tick.inner, tick.outer);
+                            
+                            let stroke_color = if has_passed { "url(#goldGradient)" } else if is_five { "#666" } else { "#222" };
+                            let stroke_width = if is_primary { "6" } else if is_five { "2.5" } else { "1" };
+                            let opacity = if has_passed { "1.0" } else if is_five { "0.4" } else { "0.15" };
+
+                            rsx! {
+                                line { 
+                                    x1: "{x1}", y1: "{y1}", x2: "{x2}", y2: "{y2}", 
+                                    stroke: "{stroke_color}", 
+                                    stroke_width: "{stroke_width}",
+                                    opacity: "{opacity}",
+                                    stroke_linecap: "round"
+                                }
+                            }
+                        }
+                    }
+
+                    // Minute Numerals (Subtle Guidance)
+// Minute Numerals (Corrected Temporal Guidance)
+                    for numeral in layout.numerals.iter() {
+                        {
+                            // The minute value (0, 5, 10... 55) on a radius of 225
+                            let m = numeral.minute;
+                            let (x, y) = numeral.at;
+
+                            // Highlight logic for the current 5-minute block
+                            let opacity = if numeral.current { "0.6" } else { "0.15" };
+
+                            rsx! {
+                                text {
+                                    x: "{x}", y: "{y}", 
+                                    text_anchor: "middle", 
+                                    alignment_baseline: "middle",
+                                    fill: "rgba(212, 175, 55, {opacity})",
+                                    font_size: "12", 
+                                    font_weight: "600",
+                                    style: "font-family: var(--font-body); transition: all 0.5s ease;",
+                                    // Ensure double digits (00, 05, 10...)
+                                    "{m:02}"
+                                }
+                            }
+                        }
+                    }
+
+                    // Full Circular 360° Celestial Aura (Omni-Glow Foundation)
+                    circle { 
+                        cx: "400", cy: "400", r: "241", fill: "none", 
+                        stroke: "url(#goldGradient)", stroke_width: "8",
+                        opacity: "0.9",
+                        filter: "url(#luxuryGlow)"
+                    }
+                    // Animated Breathing Ring (Universal Pulse)
+                    circle { 
+                        cx: "400", cy: "400", r: "241", fill: "none", 
+                        class: "rim-glow-breathe",
+                        stroke: "rgba(212, 175, 55, 0.5)", stroke_width: "16",
+                        filter: "url(#luxuryGlow)"
+                    }
+
+                    // Orbital Minute Indicator Path
+                    circle { 
+                        cx: "400", cy: "400", r: "230", fill: "none", 
+                        stroke: "rgba(212, 175, 55, 0.05)", stroke_width: "1" 
+                    }
+
+
+                    // Daily Progress Highlight Arc (Adds extra intensity to passed time)
+                    circle { 
+                        cx: "400", cy: "400", r: "241", fill: "none", 
+                        stroke: "url(#goldGradient)", stroke_width: "4",
+                        stroke_dasharray: "{stroke_dasharray} 1514",
+                        stroke_linecap: "round",
+                        style: "transform: rotate(-90deg); transform-origin: 400px 400px; opacity: 1.0; filter: url(#luxuryGlow);"
+                    }
+
+                    // Markers
+                    for (h, &(x, y)) in (0..12u32).zip(layout.markers.iter()) {
+                        {
+                            
+                            let hour = if dial_pm() { h + 12 } else { h };
+                            let key = NoteKey::new(dial_date, hour);
+                            let is_active = dial_date == today && t.hour() == hour;
+                            let state = key.map_or(HourState::Empty, |key| HourState::of(day_notes.get(&key)));
+                            let is_quadrant = h % 3 == 0;
+                            
+                            let marker_radius = if is_active { "12" } else if is_quadrant { "8" } else if state != HourState::Empty { "7" } else { "4" };
+                            // Banked hours glow solid gold, planned ones are hollow dashed rings
+                            let marker_fill = match state {
+                                HourState::Banked => "#FFD700",
+                                HourState::Planned => "#080808",
+                                HourState::Empty if is_active || is_quadrant => "#FCF6BA",
+                                HourState::Empty => "#333",
+                            };
+                            let marker_dash = if state == HourState::Planned { "3 2" } else { "none" };
+                            let marker_filter = if state == HourState::Banked { "url(#luxuryGlow)" } else { "none" };
+                            let text_fill = if is_active || is_quadrant { "#FCF6BA" } else { "#444" };
+                            let text_size = if is_active { "26" } else if is_quadrant { "18" } else { "14" };
+                            let display_h = if h == 0 { 12 } else { h };
+                            
+                            rsx! {
+                                g {
+                                    onclick: move |_| selected.set(key),
+                                    style: "cursor: pointer;",
+                                    circle {
+                                        cx: "{x}", cy: "{y}", r: "{marker_radius}",
+                                        fill: "{marker_fill}",
+                                        stroke: "url(#goldGradient)", stroke_width: "1.5",
+                                        stroke_dasharray: "{marker_dash}",
+                                        filter: "{marker_filter}",
+                                        style: "transition: all 0.4s ease;"
+                                    }
+                                    text {
+                                        x: "{x}", y: "{y}", dy: "-30", text_anchor: "middle",
+                                        fill: "{text_fill}",
+                                        font_size: "{text_size}",
+                                        font_weight: if is_active || is_quadrant { "700" } else { "200" },
+                                        style: "font-family: var(--font-display); transition: all 0.4s ease;",
+                                        "{display_h}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // Hands and Spirit Dot, turned by CSS between epochs
+                    DialHands { epoch, presence }
+                    // Refined Circular Hub with Pulsing Center Pin
+                    circle { 
+                        cx: "400", cy: "400", r: "22", 
+                        fill: "url(#goldGradient)", 
+                        filter: "url(#luxuryGlow)",
+                        style: "transform-origin: 400px 400px; animation: pulse-hub 6s infinite ease-in-out;"
+                    }
+                    circle { cx: "400", cy: "400", r: "5", fill: "#FCF6BA", filter: "url(#luxuryGlow)" }
+                }
+            }
+
+            // 3. UI Overlays (Absolute Corners for Center Focus)
+            
+            // Header: Branding
+            div {
+                style: "position: absolute; top: 7%; text-align: center; width: 100%; z-index: 50;",
+                h1 { 
+                    class: "gold-text", 
+                    style: "font-size: 1.9rem; letter-spacing: 22px; margin: 0; font-weight: 900; line-height: 1.2; text-transform: uppercase;", 
+                    "CHRONOS PLANTACERIUM" 
+                }
+                div { 
+                    style: "color: #D4AF37; letter-spacing: 11px; font-size: 0.7rem; margin-top: 9px; opacity: 0.7; font-family: var(--font-display); font-weight: 700;", 
+                    "AETERNUM PRECISION ARCHIVE" 
+                }
+            }
+
+            // Top Left: Date Navigator (drives the dial and the observation modal)
+            div {
+                style: "position: absolute; top: 6%; left: 6%; z-index: 60; display: flex; flex-direction: column; gap: 12px; background: rgba(5,5,5,0.7); padding: 18px 28px; border: 1px solid rgba(212,175,55,0.15); border-radius: 24px; backdrop-filter: blur(25px);",
+                div {
+                    style: "display: flex; align-items: center; gap: 16px;",
+                    button {
+                        class: "nav-btn",
+                        onclick: move |_| viewed_date.set(dial_date.pred_opt()),
+                        "‹"
+                    }
+                    div { class: "gold-text", style: "font-family: var(--font-display); font-weight: 700; letter-spacing: 4px; min-width: 190px; text-align: center; text-transform: uppercase;", "{dial_date_label}" }
+                    button {
+                        class: "nav-btn",
+                        onclick: move |_| viewed_date.set(dial_date.succ_opt()),
+                        "›"
+                    }
+                }
+                div {
+                    style: "display: flex; align-items: center; justify-content: space-between; gap: 12px;",
+                    input {
+                        class: "nav-date",
+                        r#type: "date",
+                        value: "{dial_date_value}",
+                        onchange: move |e| {
+                            if let Ok(date) = NaiveDate::parse_from_str(&e.value(), "%Y-%m-%d") {
+                                viewed_date.set(Some(date));
+                            }
+                        }
+                    }
+                    button {
+                        class: "nav-btn",
+                        style: "font-size: 0.65rem; letter-spacing: 3px; padding: 6px 14px;",
+                        disabled: viewed_date().is_none(),
+                        onclick: move |_| viewed_date.set(None),
+                        "TODAY"
+                    }
+                    button {
+                        class: "nav-btn",
+                        style: "font-size: 0.65rem; letter-spacing: 3px; padding: 6px 14px;",
+                        onclick: move |_| calendar_month.set(dial_date.with_day(1)),
+                        "CALENDAR"
+                    }
+                    button {
+                        class: "nav-btn",
+                        style: "font-size: 0.65rem; letter-spacing: 3px; padding: 6px 14px;",
+                        onclick: move |_| search.set(Some(SearchPanel::default())),
+                        "SEARCH"
+                    }
+                }
+            }
+
+            // Bottom Left: Units of Presence
+            div {
+                style: "position: absolute; bottom: 6%; left: 6%; display: flex; flex-direction: column; background: rgba(5,5,5,0.7); padding: 25px 45px; border: 1px solid rgba(212,175,55,0.15); border-radius: 24px; backdrop-filter: blur(25px); z-index: 50;",
+                div { style: "font-size: 0.75rem; color: #555; letter-spacing: 5px; text-transform: uppercase; margin-bottom: 5px;", "Units of Experience" }
+                ExperienceCounter { now: clock.now }
+            }
+
+            // Bottom Right: Meridiem Toggle & Secure State Button
+            div {
+                style: "position: absolute; bottom: 6%; right: 6%; z-index: 50; display: flex; flex-direction: column; align-items: center; gap: 18px;",
+                button {
+                    class: "luxury-btn",
+                    style: "padding: 10px 30px; font-size: 0.7rem; min-width: 280px; background: rgba(5,5,5,0.7); color: #D4AF37; border: 1px solid rgba(212,175,55,0.3);",
+                    onclick: move |_| dial_pm.toggle(),
+                    "{meridiem_label}"
+                }
+                button { 
+                    class: "luxury-btn", 
+                    style: "padding: 15px 45px; font-size: 0.9rem; backdrop-filter: blur(10px); min-width: 280px;",
+                    onclick: on_save, 
+                    "Secure State" 
+                }
+            }
+
+            // Footer: Versioning (Centered deeply)
+            div {
+                style: "position: absolute; bottom: 3%; width: 100%; text-align: center; opacity: 0.35; letter-spacing: 8px; font-size: 0.65rem; font-family: var(--font-display); pointer-events: none;",
+                "LIFE BANK EXPERIENCE V1 • TIME ANCHOR SYSTEM"
+            }
+
+            // Save Confirmation Notification
+            if let Some(report) = integrity() {
+                {
+                    let passed = report.passed();
+                    let digest = report.digest.get(..16).unwrap_or(&report.digest).to_string();
+                    let hidden = report.problems.len().saturating_sub(6);
+                    rsx! {
+                        div {
+                            class: if passed { "save-status" } else { "save-status integrity-failed" },
+                            style: "cursor: pointer;",
+                            onclick: move |_| integrity.set(None),
+                            div { if passed { "TIME VAULT SECURED" } else { "VAULT INTEGRITY COMPROMISED" } }
+                            div { class: "integrity-detail",
+                                "{report.notes} observations · {report.banked} banked · {report.days} days"
+                            }
+                            div { class: "integrity-detail", "SHA-256 {digest}…" }
+                            for problem in report.problems.iter().take(6) {
+                                div { class: "integrity-problem", "{problem}" }
+                            }
+                            if hidden > 0 {
+                                div { class: "integrity-problem", "…and {hidden} more" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Calendar: archive coverage per day, click through to that day's dial
+            if let Some(first) = calendar_month() {
+                {
+                    let density = month_density(&vault.read(), first);
+                    let month_label = first.format("%B %Y").to_string();
+                    let leading = first.weekday().num_days_from_monday();
+                    let days: Vec<NaiveDate> = first.iter_days().take_while(|d| d.month() == first.month()).collect();
+                    let written: usize = density.values().map(|(w, _)| w).sum();
+                    let covered = density.len();
+                    rsx! {
+                        div {
+                            style: "position: absolute; top: 0; left: 0; width: 100vw; height: 100vh; background: rgba(0,0,0,0.9); backdrop-filter: blur(30px); display: flex; justify-content: center; align-items: center; z-index: 900;",
+                            onclick: move |_| calendar_month.set(None),
+                            div {
+                                style: "width: 720px; background: #080808; border: 1px solid #1a1a1a; padding: 50px 60px; border-radius: 2px; box-shadow: 0 60px 120px rgba(0,0,0,1); display: flex; flex-direction: column; gap: 28px;",
+                                onclick: move |e| e.stop_propagation(),
+                                header {
+                                    style: "display: flex; justify-content: space-between; align-items: center;",
+                                    div {
+                                        style: "display: flex; gap: 8px;",
+                                        button { class: "nav-btn", title: "Previous year", onclick: move |_| calendar_month.set(first.checked_sub_months(Months::new(12))), "«" }
+                                        button { class: "nav-btn", title: "Previous month", onclick: move |_| calendar_month.set(first.checked_sub_months(Months::new(1))), "‹" }
+                                    }
+                                    h2 { class: "gold-text", style: "font-family: var(--font-display); margin: 0; font-size: 1.8rem; letter-spacing: 10px; font-weight: 900; text-transform: uppercase;", "{month_label}" }
+                                    div {
+                                        style: "display: flex; gap: 8px;",
+                                        button { class: "nav-btn", title: "Next month", onclick: move |_| calendar_month.set(first.checked_add_months(Months::new(1))), "›" }
+                                        button { class: "nav-btn", title: "Next year", onclick: move |_| calendar_month.set(first.checked_add_months(Months::new(12))), "»" }
+                                    }
+                                }
+                                div {
+                                    class: "calendar-grid",
+                                    for weekday in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"] {
+                                        div { class: "calendar-weekday", "{weekday}" }
+                                    }
+                                    for _ in 0..leading {
+                                        div {}
+                                    }
+                                    for date in days {
+                                        {
+                                            let (hours, banked) = density.get(&date).copied().unwrap_or_default();
+                                            let class = format!("calendar-day heat-{}{}", coverage_level(hours), if date == today { " calendar-today" } else { "" });
+                                            rsx! {
+                                                div {
+                                                    class: "{class}",
+                                                    title: "{hours} of 24 hours written · {banked} banked",
+                                                    onclick: move |_| {
+                                                        viewed_date.set((date != today).then_some(date));
+                                                        calendar_month.set(None);
+                                                    },
+                                                    div { "{date.day()}" }
+                                                    if hours > 0 {
+                                                        div { class: "calendar-count", "{hours}h" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                div {
+                                    style: "display: flex; justify-content: space-between; align-items: center; font-size: 0.7rem; color: #555; letter-spacing: 3px; text-transform: uppercase;",
+                                    div { "{written} hours written across {covered} days" }
+                                    div {
+                                        style: "display: flex; align-items: center; gap: 6px;",
+                                        "Sparse"
+                                        for level in 0..5 {
+                                            div { class: "calendar-swatch heat-{level}" }
+                                        }
+                                        "Dense"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Search: ranked matches across the whole archive, click to open the hour
+            if let Some(panel) = search() {
+                {
+                    let hits = vault.read().index.search(&panel.query());
+                    let total = hits.len();
+                    let date_value = |d: Option<NaiveDate>| d.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+                    let from_value = date_value(panel.from);
+                    let to_value = date_value(panel.to);
+                    let searching = !panel.text.trim().is_empty() || panel.from.is_some() || panel.to.is_some();
+                    rsx! {
+                        div {
+                            style: "position: absolute; top: 0; left: 0; width: 100vw; height: 100vh; background: rgba(0,0,0,0.9); backdrop-filter: blur(30px); display: flex; justify-content: center; align-items: flex-start; padding-top: 10vh; z-index: 900;",
+                            onclick: move |_| search.set(None),
+                            div {
+                                style: "width: 820px; max-height: 75vh; background: #080808; border: 1px solid #1a1a1a; padding: 45px 55px; border-radius: 2px; box-shadow: 0 60px 120px rgba(0,0,0,1); display: flex; flex-direction: column; gap: 22px;",
+                                onclick: move |e| e.stop_propagation(),
+                                input {
+                                    class: "search-input",
+                                    autofocus: true,
+                                    placeholder: "Search the archive — words, \"phrases\", prefix*",
+                                    value: "{panel.text}",
+                                    oninput: move |e| {
+                                        if let Some(panel) = search.write().as_mut() {
+                                            panel.text = e.value();
+                                        }
+                                    },
+                                    onkeydown: move |e| {
+                                        if e.key() == Key::Escape {
+                                            search.set(None);
+                                        }
+                                    }
+                                }
+                                div {
+                                    style: "display: flex; align-items: center; gap: 12px; font-size: 0.65rem; color: #555; letter-spacing: 3px; text-transform: uppercase;",
+                                    "From"
+                                    input {
+                                        class: "nav-date",
+                                        r#type: "date",
+                                        value: "{from_value}",
+                                        onchange: move |e| {
+                                            if let Some(panel) = search.write().as_mut() {
+                                                panel.from = NaiveDate::parse_from_str(&e.value(), "%Y-%m-%d").ok();
+                                            }
+                                        }
+                                    }
+                                    "To"
+                                    input {
+                                        class: "nav-date",
+                                        r#type: "date",
+                                        value: "{to_value}",
+                                        onchange: move |e| {
+                                            if let Some(panel) = search.write().as_mut() {
+                                                panel.to = NaiveDate::parse_from_str(&e.value(), "%Y-%m-%d").ok();
+                                            }
+                                        }
+                                    }
+                                    div { style: "flex: 1; text-align: right;",
+                                        if searching { "{total} observations" }
+                                    }
+                                }
+                                div {
+                                    style: "overflow-y: auto; display: flex; flex-direction: column; gap: 4px;",
+                                    for hit in hits.into_iter().take(SEARCH_RESULTS) {
+                                        {
+                                            let key = hit.key;
+                                            let (h, meridiem) = geometry::twelve_hour(key.hour);
+                                            let label = format!("{} · {h} {meridiem}", key.date.format("%a %d %b %Y"));
+                                            rsx! {
+                                                div {
+                                                    class: "search-hit",
+                                                    onclick: move |_| {
+                                                        open_node(key, viewed_date, dial_pm, selected);
+                                                        search.set(None);
+                                                    },
+                                                    div { class: "search-hit-key", "{label}" }
+                                                    div { class: "search-hit-snippet",
+                                                        for (text, is_match) in hit.snippet {
+                                                            if is_match {
+                                                                mark { "{text}" }
+                                                            } else {
+                                                                span { "{text}" }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Vault Recovery Dialog (blocks everything until the user decides)
+            if let Some(recovery) = vault.read().recovery.clone() {
+                {
+                    let salvaged = recovery.salvaged.len();
+                    let path = recovery.path.display().to_string();
+                    let quarantine = recovery.quarantine.display().to_string();
+                    rsx! {
+                        div {
+                            style: "position: absolute; top: 0; left: 0; width: 100vw; height: 100vh; background: rgba(0,0,0,0.96); backdrop-filter: blur(40px); display: flex; justify-content: center; align-items: center; z-index: 2000;",
+                            div {
+                                style: "width: 720px; background: #080808; border: 1px solid #8B2500; padding: 60px; border-radius: 2px; box-shadow: 0 60px 120px rgba(0,0,0,1); display: flex; flex-direction: column; gap: 28px;",
+                                h2 { class: "gold-text", style: "font-family: var(--font-display); margin: 0; font-size: 2.2rem; letter-spacing: 14px; font-weight: 900;", "VAULT RECOVERY" }
+                                div { style: "color: #F5C6A5; line-height: 1.8;", "{path} could not be read: {recovery.cause}" }
+                                div { style: "color: #ccc; line-height: 1.8;",
+                                    "The damaged archive has been preserved as {quarantine}. {salvaged} observations could be salvaged from it. "
+                                    "Nothing will be written to the vault until you decide."
+                                }
+                                div {
+                                    style: "display: flex; gap: 20px; justify-content: flex-end;",
+                                    button {
+                                        class: "luxury-btn",
+                                        style: "padding: 12px 30px; font-size: 0.75rem; background: rgba(5,5,5,0.7); color: #D4AF37; border: 1px solid rgba(212,175,55,0.3);",
+                                        onclick: move |_| vault.write().decline_recovery(),
+                                        "Leave Untouched"
+                                    }
+                                    button {
+                                        class: "luxury-btn",
+                                        style: "padding: 12px 30px; font-size: 0.75rem;",
+                                        onclick: move |_| vault.write().confirm_recovery(),
+                                        "Restore {salvaged} Observations"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Persistence Failure Notification (stays until a write succeeds)
+            if let Some(err) = vault.read().error.clone() {
+                div { class: "save-status vault-error", "VAULT UNSECURED — {err}" }
+            }
+
+            // 4. Modal: Temporal Observation Vault
+            if let Some(date_key) = selected() {
+                {
+                    let now = Local::now().naive_local();
+                    let note = vault.read().get(date_key);
+                    let state = HourState::of(note.as_ref());
+                    let note_content = note.map(|n| n.content).unwrap_or_default();
+                    let is_future = !date_key.has_begun(now);
+                    let banked_at = vault.read().ledger.entry(date_key).map(|e| e.banked_at.clone()).unwrap_or_default();
+                    let preview = render_note(&note_content, &WikiLinks { date: date_key.date, href: &|key| Some(format!("#{key}")) });
+                    let backlinks: Vec<(NoteKey, String)> = vault
+                        .read()
+                        .links
+                        .to(date_key)
+                        .into_iter()
+                        .map(|source| {
+                            let (h, meridiem) = geometry::twelve_hour(source.hour);
+                            (source, format!("{} · {h} {meridiem}", source.date.format("%a %d %b %Y")))
+                        })
+                        .collect();
+                    let node_date = date_key.date.format("%A %d %B %Y").to_string();
+                    let node_kind = match state {
+                        HourState::Banked => "Banked Observation Node",
+                        HourState::Planned if is_future => "Planned Observation Node",
+                        _ => "Temporal Observation Node",
+                    };
+                    let lock_label = if state == HourState::Banked { "Close Node" } else if is_future { "Keep Planned" } else { "Lock Node" };
+                    
+                    rsx! {
+                        div {
+                            style: "position: absolute; top: 0; left: 0; width: 100vw; height: 100vh; background: rgba(0,0,0,0.96); backdrop-filter: blur(40px); display: flex; justify-content: center; align-items: center; z-index: 1000;",
+                            onclick: move |_| selected.set(None),
+                            div {
+                                style: "width: 850px; height: 85vh; background: #080808; border: 1px solid #1a1a1a; padding: 70px; border-radius: 2px; box-shadow: 0 60px 120px rgba(0,0,0,1); display: flex; flex-direction: column; gap: 40px;",
+                                onclick: move |e| e.stop_propagation(),
+                                
+                                header {
+                                    style: "display: flex; justify-content: space-between; align-items: center;",
+                                    div {
+                                        h2 { class: "gold-text", style: "font-family: var(--font-display); margin: 0; font-size: 3.5rem; letter-spacing: 20px; font-weight: 900;", "HOUR {display_modal_h}" }
+                                        div { style: "font-size: 0.9rem; color: #444; letter-spacing: 12px; text-transform: uppercase; margin-top: 10px;", "{node_kind}" }
+                                        div { style: "font-size: 0.75rem; color: #AA771C; letter-spacing: 6px; text-transform: uppercase; margin-top: 8px; font-family: var(--font-display);", "{node_date}" }
+                                    }
+                                    button {
+                                        class: "luxury-btn",
+                                        style: "padding: 12px 30px; font-size: 0.8rem;",
+                                        onclick: move |_| {
+                                            spawn(bank(vault, vec![date_key]));
+                                            selected.set(None);
+                                        },
+                                        "{lock_label}"
+                                    }
+                                }
+
+                                // Banked hours are sealed: no input, only the record
+                                if state == HourState::Banked {
+                                    div {
+                                        style: "font-size: 0.75rem; color: #AA771C; letter-spacing: 6px; text-transform: uppercase; font-family: var(--font-display);",
+                                        if banked_at.is_empty() { "Sealed into the ledger" } else { "Sealed into the ledger · {banked_at}" }
+                                    }
+                                } else {
+                                    // Input Section
+                                    textarea {
+                                        style: "height: 220px; background: #000; color: #FCF6BA; border: 1px solid #1a1a1a; padding: 35px; font-family: var(--font-body); font-size: 1.2rem; outline: none; line-height: 1.8; resize: none;",
+                                        value: "{note_content}",
+                                        placeholder: "Commit the essence of this temporal anchor to memory...",
+                                        oninput: move |e| edit(vault, date_key, e.value())
+                                    }
+                                }
+
+                                // Preview Section (Stacked Below Input)
+                                div {
+                                    style: "flex: 1; overflow-y: auto; padding: 50px; background: rgba(10,10,10,0.5); border: 1px solid rgba(212,175,55,0.08);",
+                                    div {
+                                        // Task boxes of unbanked notes can be ticked
+                                        class: if state == HourState::Banked { "markdown-body" } else { "markdown-body tasks-live" },
+                                        dangerous_inner_html: "{preview}"
+                                    }
+                                }
+
+                                // Backlinks: every note whose wiki-links name this hour
+                                if !backlinks.is_empty() {
+                                    div {
+                                        class: "backlinks",
+                                        div { class: "backlinks-title", "Linked from" }
+                                        for (source, label) in backlinks {
+                                            div {
+                                                class: "backlink",
+                                                onclick: move |_| open_node(source, viewed_date, dial_pm, selected),
+                                                "{label}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
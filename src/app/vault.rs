@@ -0,0 +1,314 @@
+use chronos_aeternum::config::Config;
+use chronos_aeternum::integrity::IntegrityReport;
+use chronos_aeternum::ledger::Ledger;
+use chronos_aeternum::links::Backlinks;
+use chronos_aeternum::note::{NoteKey, Notes, TimeNote};
+use chronos_aeternum::search::SearchIndex;
+use chronos_aeternum::store::{self, MemoryStore, NoteStore, Recovery, StoreError};
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+// --- Vault State ---
+
+/// Quiet period after the last keystroke before edits are written out.
+pub const SAVE_DEBOUNCE: Duration = Duration::from_millis(600);
+
+/// Edits not yet handed to the store, coalesced per key (`None` = deleted).
+type PendingEdits = BTreeMap<NoteKey, Option<TimeNote>>;
+
+/// The open archive together with unsaved edits and the last persistence
+/// failure, so the UI can show it instead of silently losing writes.
+pub struct Vault {
+    pub config: Config,
+    // Shared with the background save task
+    pub store: Arc<Mutex<Box<dyn NoteStore>>>,
+    pub ledger: Ledger,
+    // Full-text index over every note, kept current as edits are staged
+    pub index: SearchIndex,
+    // Which notes wiki-link to which hours, kept current the same way
+    pub links: Backlinks,
+    pub pending: PendingEdits,
+    // Bumped on every edit so a debounced save can tell it has been superseded
+    pub revision: u64,
+    pub error: Option<String>,
+    // Set when the configured backend could not be opened and nothing is being saved
+    pub volatile: bool,
+    // A damaged archive awaiting the user's decision; nothing is written meanwhile
+    pub recovery: Option<Recovery>,
+}
+
+impl Vault {
+    pub fn open() -> Self {
+        let config = Config::load();
+        let (store, error, volatile, recovery): (Box<dyn NoteStore>, _, _, _) = match store::open(&config) {
+            Ok(store) => (store, None, false, None),
+            // Show what was salvaged, but keep it off the disk until confirmed
+            Err(StoreError::Corrupt(recovery)) => (
+                Box::new(MemoryStore::new(recovery.salvaged.clone())),
+                Some("vault in recovery — observations are not being saved".into()),
+                true,
+                Some(*recovery),
+            ),
+            Err(e) => (
+                Box::new(MemoryStore::default()),
+                Some(format!("{e} — observations are not being saved")),
+                true,
+                None,
+            ),
+        };
+        let (ledger, error) = match Ledger::open(config.ledger_path()) {
+            Ok(ledger) => (ledger, error),
+            Err(e) => (Ledger::default(), Some(format!("banking ledger unavailable: {e}"))),
+        };
+        let (index, links, error) = match store.iter() {
+            Ok(notes) => {
+                let notes: Notes = notes.collect();
+                (SearchIndex::build(&notes), Backlinks::build(&notes), error)
+            }
+            Err(e) => (SearchIndex::default(), Backlinks::default(), error.or(Some(format!("search index unavailable: {e}")))),
+        };
+        Vault { config, store: Arc::new(Mutex::new(store)), ledger, index, links, pending: PendingEdits::new(), revision: 0, error, volatile, recovery }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Box<dyn NoteStore>> {
+        lock_store(&self.store)
+    }
+
+    /// The note for `key`, including edits that have not been saved yet.
+    pub fn get(&self, key: NoteKey) -> Option<TimeNote> {
+        match self.pending.get(&key) {
+            Some(edit) => edit.clone(),
+            None => self.lock().get(key).ok().flatten(),
+        }
+    }
+
+    /// Every note dated `from..=to` (either end open if `None`), including
+    /// edits that have not been saved yet.
+    pub fn range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Notes {
+        let mut notes = self.lock().range(from, to).unwrap_or_default();
+        let Some(span) = NoteKey::span(from, to) else { return notes };
+        for (key, edit) in self.pending.range(span) {
+            match edit {
+                Some(note) => notes.insert(*key, note.clone()),
+                None => notes.remove(key),
+            };
+        }
+        notes
+    }
+
+    pub fn day(&self, date: NaiveDate) -> Notes {
+        self.range(Some(date), Some(date))
+    }
+
+    /// Planned hours dated `since` onwards (the whole archive if `None`)
+    /// that have fully elapsed by `now`.
+    pub fn due_for_banking(&self, since: Option<NaiveDate>, now: NaiveDateTime) -> Vec<NoteKey> {
+        self.range(since, Some(now.date()))
+            .into_iter()
+            .filter(|(key, note)| !note.is_locked && key.has_elapsed(now))
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// Records an edit for the next save and returns the new revision.
+    pub fn stage(&mut self, key: NoteKey, note: Option<TimeNote>) -> u64 {
+        self.index.update(key, note.as_ref());
+        self.links.update(key, note.as_ref());
+        self.pending.insert(key, note);
+        self.revision += 1;
+        self.revision
+    }
+
+    /// Synchronously writes everything out; used when the window closes.
+    pub fn flush_now(&mut self) -> bool {
+        let batch = std::mem::take(&mut self.pending);
+        let result = apply(&self.store, &batch, false);
+        if result.is_err() {
+            self.pending = batch;
+        }
+        self.record(result)
+    }
+
+    /// Writes the salvaged notes over the damaged archive and resumes saving.
+    pub fn confirm_recovery(&mut self) {
+        let Some(recovery) = self.recovery.take() else { return };
+        match store::restore(&self.config, &recovery) {
+            Ok(store) => {
+                self.store = Arc::new(Mutex::new(store));
+                self.volatile = false;
+                self.error = None;
+            }
+            Err(e) => {
+                self.error = Some(e.to_string());
+                self.recovery = Some(recovery);
+            }
+        }
+    }
+
+    /// Leaves the damaged archive untouched for the rest of the session.
+    pub fn decline_recovery(&mut self) {
+        if self.recovery.take().is_some() {
+            self.error = Some("recovery declined — archive left untouched, observations are not being saved".into());
+        }
+    }
+
+    fn record(&mut self, result: Result<(), StoreError>) -> bool {
+        match result {
+            Ok(()) => {
+                if !self.volatile {
+                    self.error = None;
+                }
+                !self.volatile
+            }
+            Err(e) => {
+                eprintln!("chronos: {e}");
+                self.error = Some(e.to_string());
+                false
+            }
+        }
+    }
+}
+
+fn lock_store(store: &Mutex<Box<dyn NoteStore>>) -> MutexGuard<'_, Box<dyn NoteStore>> {
+    // A panic mid-write leaves nothing half-applied worth refusing to read
+    store.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Hands a batch of edits to the store and flushes (or snapshots) it.
+fn apply(store: &Mutex<Box<dyn NoteStore>>, batch: &PendingEdits, snapshot: bool) -> Result<(), StoreError> {
+    let mut store = lock_store(store);
+    for (key, edit) in batch {
+        match edit {
+            Some(note) => store.put(*key, note.clone())?,
+            None => {
+                store.delete(*key)?;
+            }
+        }
+    }
+    if snapshot { store.snapshot() } else { store.flush() }
+}
+
+/// "Secure State": writes and snapshots the vault, then re-reads it from
+/// storage and reports what was found. `None` if the write itself failed.
+pub async fn secure(mut vault: Signal<Vault>) -> Option<IntegrityReport> {
+    if !persist(vault, true).await {
+        return None;
+    }
+    let (store, ledger) = {
+        let vault = vault.peek();
+        (vault.store.clone(), vault.ledger.clone())
+    };
+    let result = tokio::task::spawn_blocking(move || {
+        let store = lock_store(&store);
+        let mut report = store.verify()?;
+        let notes: Notes = store.iter()?.collect();
+        report.problems.extend(ledger.verify(&notes));
+        Ok(report)
+    })
+        .await
+        .unwrap_or_else(|e| Err(StoreError::Io(io::Error::other(e))));
+    match result {
+        Ok(report) => Some(report),
+        Err(e) => {
+            vault.write().record(Err(e));
+            None
+        }
+    }
+}
+
+/// Banks the notes at `keys`: locks them, writes them out, then chains each
+/// into the ledger; hours that fail either step go back to planned. An hour
+/// is banked once and only after it has begun; empty, future or
+/// already-banked hours are left alone.
+pub async fn bank(mut vault: Signal<Vault>, keys: Vec<NoteKey>) {
+    let now = Local::now();
+    let notes: Vec<(NoteKey, TimeNote)> = {
+        let vault = vault.peek();
+        keys.into_iter()
+            .filter(|key| key.has_begun(now.naive_local()) && vault.ledger.entry(*key).is_none())
+            .filter_map(|key| vault.get(key).filter(|note| !note.is_locked).map(|note| (key, note)))
+            .map(|(key, note)| (key, TimeNote { is_locked: true, ..note }))
+            .collect()
+    };
+    if notes.is_empty() {
+        return;
+    }
+    for (key, note) in &notes {
+        vault.write().stage(*key, Some(note.clone()));
+    }
+    if !persist(vault, false).await {
+        replan(&mut vault.write(), notes);
+        return;
+    }
+    let unbanked = {
+        let mut vault = vault.write();
+        let banked = notes
+            .iter()
+            .take_while(|(key, note)| {
+                let result = vault.ledger.append(*key, note, now);
+                let appended = result.is_ok();
+                vault.record(result);
+                appended
+            })
+            .count();
+        replan(&mut vault, notes.into_iter().skip(banked))
+    };
+    if unbanked {
+        persist(vault, false).await;
+    }
+}
+
+/// Stages `notes` as planned again, rather than leaving them locked without
+/// a ledger entry. Returns whether there were any.
+fn replan(vault: &mut Vault, notes: impl IntoIterator<Item = (NoteKey, TimeNote)>) -> bool {
+    let mut any = false;
+    for (key, note) in notes {
+        vault.stage(key, Some(TimeNote { is_locked: false, ..note }));
+        any = true;
+    }
+    any
+}
+
+/// Stages `content` as the planned note for `key` (empty deletes it) and
+/// saves once edits pause; later edits supersede this one. A banked hour
+/// is never written, even if it was banked while the edit was being typed.
+pub fn edit(mut vault: Signal<Vault>, key: NoteKey, content: String) {
+    if vault.peek().get(key).is_some_and(|note| note.is_locked) {
+        return;
+    }
+    let note = (!content.is_empty()).then_some(TimeNote { content, is_locked: false });
+    let revision = vault.write().stage(key, note);
+    spawn(async move {
+        tokio::time::sleep(SAVE_DEBOUNCE).await;
+        if vault.peek().revision == revision {
+            persist(vault, false).await;
+        }
+    });
+}
+
+/// Writes pending edits on a blocking worker so the UI thread never waits on
+/// the disk. Edits made while the save is in flight stay pending for the next one.
+pub async fn persist(mut vault: Signal<Vault>, snapshot: bool) -> bool {
+    let (store, batch) = {
+        let vault = vault.peek();
+        if vault.pending.is_empty() && !snapshot {
+            return true;
+        }
+        (vault.store.clone(), vault.pending.clone())
+    };
+    let result = tokio::task::spawn_blocking(move || apply(&store, &batch, snapshot).map(|()| batch))
+        .await
+        .unwrap_or_else(|e| Err(StoreError::Io(io::Error::other(e))));
+    let mut vault = vault.write();
+    match result {
+        Ok(batch) => {
+            vault.pending.retain(|key, edit| batch.get(key) != Some(edit));
+            vault.record(Ok(()))
+        }
+        Err(e) => vault.record(Err(e)),
+    }
+}
@@ -0,0 +1,276 @@
+use chronos_aeternum::config::Config;
+use chronos_aeternum::export;
+use chronos_aeternum::ledger::Ledger;
+use chronos_aeternum::note::{HourState, NoteKey, Notes, TimeNote};
+use chronos_aeternum::search::{Query, SearchIndex};
+use chronos_aeternum::store;
+use chrono::{Local, NaiveDate, Timelike, Utc};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// --- Command Line ---
+
+/// Chronos Aeternum: run without a command to open the dial, or script the
+/// archive headlessly with one of the commands below.
+#[derive(Parser)]
+#[command(version)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Write an observation, appending to any note already in that hour
+    Add {
+        /// Day of the observation (YYYY-MM-DD); defaults to today
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// Hour of the observation (0-23); defaults to the current hour
+        #[arg(long, value_parser = clap::value_parser!(u32).range(0..24))]
+        hour: Option<u32>,
+        /// Replace the hour's note instead of appending to it
+        #[arg(long)]
+        replace: bool,
+        /// Markdown text; read from stdin when omitted
+        text: Vec<String>,
+    },
+    /// Print the observations of one hour (YYYY-MM-DD-HH) or a whole day (YYYY-MM-DD)
+    Show { when: String },
+    /// List observations, one per line
+    List(Range),
+    /// Search every observation: words, "quoted phrases", prefix*, from:/to: dates
+    Search {
+        #[command(flatten)]
+        range: Range,
+        /// Most results to print
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        query: Vec<String>,
+    },
+    /// Write the archive out in another format
+    Export {
+        #[command(flatten)]
+        range: Range,
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+        /// Destination; stdout when omitted (a folder, required, for `markdown` and `html`)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Merge an exported archive back in; banked hours are never overwritten
+    Import {
+        #[arg(long, value_enum, default_value_t = ImportFormat::Json)]
+        format: ImportFormat,
+        /// File, or folder tree for `markdown`
+        input: PathBuf,
+    },
+    /// Re-read the archive and the banking ledger and report any problems
+    Verify,
+}
+
+/// Inclusive date range shared by the listing commands.
+#[derive(Args)]
+pub struct Range {
+    /// First day to include (YYYY-MM-DD)
+    #[arg(long)]
+    from: Option<NaiveDate>,
+    /// Last day to include (YYYY-MM-DD)
+    #[arg(long)]
+    to: Option<NaiveDate>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// The vault's own `{ "YYYY-MM-DD-HH": note }` layout
+    Json,
+    /// A `YYYY/MM/DD.md` folder tree, one heading per hour
+    Markdown,
+    /// A self-contained static journal site
+    Html,
+    /// An iCalendar file, one hour-long event per observation
+    Ics,
+    /// One CSV record per observation, oldest first
+    Csv,
+    /// One JSON object per line, with the same fields as `csv`
+    Jsonl,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ImportFormat {
+    /// A file written by `export --format json`
+    Json,
+    /// A folder tree written by `export --format markdown`
+    Markdown,
+    /// An iCalendar file; events become planned observations for their hours
+    Ics,
+    /// A file written by `export --format csv`
+    Csv,
+}
+
+/// Runs `command` against the configured archive, reporting any failure on stderr.
+pub fn run(command: Command) -> ExitCode {
+    match execute(command) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("chronos: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn execute(command: Command) -> Result<ExitCode, Box<dyn Error>> {
+    let config = Config::load();
+    let mut store = store::open(&config)?;
+    let mut out = io::stdout().lock();
+
+    match command {
+        Command::Add { date, hour, replace, text } => {
+            let now = Local::now();
+            let key = NoteKey::new(date.unwrap_or(now.date_naive()), hour.unwrap_or(now.hour())).ok_or("hour must be 0-23")?;
+            let mut text = text.join(" ");
+            if text.is_empty() {
+                io::stdin().read_to_string(&mut text)?;
+            }
+            let text = text.trim_end();
+            if text.is_empty() {
+                return Err("nothing to add".into());
+            }
+            let content = match store.get(key)? {
+                Some(note) if note.is_locked => return Err(format!("{key} is banked and read-only").into()),
+                Some(note) if !replace => format!("{}\n\n{text}", note.content),
+                _ => text.to_string(),
+            };
+            store.put(key, TimeNote { content, is_locked: false })?;
+            store.flush()?;
+            writeln!(out, "{key}")?;
+        }
+        Command::Show { when } => {
+            let notes = match (NoteKey::parse(&when), NaiveDate::parse_from_str(&when, "%Y-%m-%d")) {
+                (Some(key), _) => store.get(key)?.map(|note| Notes::from([(key, note)])).unwrap_or_default(),
+                (None, Ok(date)) => store.range(Some(date), Some(date))?,
+                (None, Err(_)) => return Err(format!("`{when}` is neither YYYY-MM-DD-HH nor YYYY-MM-DD").into()),
+            };
+            if notes.is_empty() {
+                return Err(format!("no observations for {when}").into());
+            }
+            for (i, (key, note)) in notes.iter().enumerate() {
+                if i > 0 {
+                    writeln!(out)?;
+                }
+                writeln!(out, "## {key} ({})\n\n{}", state_label(note), note.content.trim_end())?;
+            }
+        }
+        Command::List(range) => {
+            for (key, note) in store.range(range.from, range.to)? {
+                let first_line = note.content.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+                writeln!(out, "{key}  {:<7}  {first_line}", state_label(&note))?;
+            }
+        }
+        Command::Search { range, limit, query } => {
+            let mut query = Query::parse(&query.join(" "));
+            query.from = range.from.or(query.from);
+            query.to = range.to.or(query.to);
+            let notes: Notes = store.iter()?.collect();
+            for hit in SearchIndex::build(&notes).search(&query).into_iter().take(limit) {
+                let snippet: String = hit
+                    .snippet
+                    .iter()
+                    .map(|(text, is_match)| if *is_match { format!("**{text}**") } else { text.clone() })
+                    .collect();
+                writeln!(out, "{}  {snippet}", hit.key)?;
+            }
+        }
+        Command::Export { range, format, output } => {
+            let notes = store.range(range.from, range.to)?;
+            let data = match format {
+                Format::Json => serde_json::to_string_pretty(&notes)? + "\n",
+                Format::Ics => export::ics::write_calendar(&notes, Utc::now()),
+                Format::Csv | Format::Jsonl => {
+                    let rows = export::table::rows(&notes, &Ledger::open(config.ledger_path())?);
+                    match format {
+                        Format::Csv => export::table::write_csv(&rows),
+                        _ => export::table::write_jsonl(&rows)?,
+                    }
+                }
+                Format::Markdown => {
+                    let root = output.ok_or("markdown export needs --output <DIR>")?;
+                    let days = export::markdown::write_tree(&root, &notes)?;
+                    eprintln!("{} observations across {days} days written to {}", notes.len(), root.display());
+                    return Ok(ExitCode::SUCCESS);
+                }
+                Format::Html => {
+                    let root = output.ok_or("html export needs --output <DIR>")?;
+                    let pages = export::html::write_site(&root, &notes)?;
+                    eprintln!("{pages} pages written to {}", root.join("index.html").display());
+                    return Ok(ExitCode::SUCCESS);
+                }
+            };
+            match output {
+                Some(path) => store::backup::write_atomic(&path, data.as_bytes())?,
+                None => write!(out, "{data}")?,
+            }
+        }
+        Command::Import { format, input } => {
+            let notes = match format {
+                ImportFormat::Json => serde_json::from_str(&fs::read_to_string(&input)?)?,
+                ImportFormat::Markdown => {
+                    let (notes, problems) = export::markdown::read_tree(&input)?;
+                    for problem in problems {
+                        eprintln!("chronos: skipped {problem}");
+                    }
+                    notes
+                }
+                ImportFormat::Ics => {
+                    let (notes, notices) = export::ics::read_calendar(&fs::read_to_string(&input)?);
+                    for notice in notices {
+                        eprintln!("chronos: {notice}");
+                    }
+                    notes
+                }
+                ImportFormat::Csv => {
+                    let (notes, problems) = export::table::read_csv(&fs::read_to_string(&input)?);
+                    for problem in problems {
+                        eprintln!("chronos: skipped {problem}");
+                    }
+                    notes
+                }
+            };
+            let mut ledger = Ledger::open(config.ledger_path())?;
+            let imported = export::merge(store.as_mut(), &mut ledger, notes, Local::now())?;
+            for skipped in &imported.skipped {
+                eprintln!("chronos: skipped {skipped}");
+            }
+            for planned in &imported.planned {
+                eprintln!("chronos: {planned}");
+            }
+            writeln!(out, "{} added · {} updated · {} unchanged · {} skipped", imported.added, imported.updated, imported.unchanged, imported.skipped.len())?;
+        }
+        Command::Verify => {
+            let mut report = store.verify()?;
+            let ledger = Ledger::open(config.ledger_path())?;
+            let notes: Notes = store.iter()?.collect();
+            report.problems.extend(ledger.verify(&notes));
+            writeln!(out, "{} observations · {} banked · {} days", report.notes, report.banked, report.days)?;
+            writeln!(out, "SHA-256 {}", report.digest)?;
+            for problem in &report.problems {
+                writeln!(out, "problem: {problem}")?;
+            }
+            if !report.passed() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn state_label(note: &TimeNote) -> &'static str {
+    match HourState::of(Some(note)) {
+        HourState::Banked => "banked",
+        _ => "planned",
+    }
+}
@@ -0,0 +1,77 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+// --- Configuration ---
+
+const CONFIG_FILE: &str = "chronos_config.json";
+pub const JSON_STORE_FILE: &str = "chronos_notes.json";
+pub const SQLITE_STORE_FILE: &str = "chronos_notes.sqlite3";
+pub const LEDGER_FILE: &str = "chronos_ledger.jsonl";
+
+/// Where and how the archive is kept. Read from `chronos_config.json`;
+/// every field is optional and falls back to the original JSON vault.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub backend: Backend,
+    /// Overrides the backend's default archive location.
+    pub path: Option<PathBuf>,
+    /// Number of rotating JSON vault snapshots to keep; 0 disables them.
+    pub backups: usize,
+    /// Bank planned hours automatically once they have elapsed. Off unless
+    /// asked for, so no existing note is sealed without the user choosing it.
+    pub auto_bank: bool,
+    /// Typefaces of the dial: the bundled Cinzel and Montserrat, or the system's.
+    pub fonts: Fonts,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { backend: Backend::default(), path: None, backups: 5, auto_bank: false, fonts: Fonts::default() }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    #[default]
+    Json,
+    Sqlite,
+    Memory,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Fonts {
+    #[default]
+    Bundled,
+    System,
+}
+
+impl Config {
+    pub fn load() -> Self {
+        match fs::read_to_string(CONFIG_FILE) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|e| {
+                eprintln!("chronos: ignoring malformed {CONFIG_FILE}: {e}");
+                Config::default()
+            }),
+            Err(_) => Config::default(),
+        }
+    }
+
+    pub fn store_path(&self) -> PathBuf {
+        self.path.clone().unwrap_or_else(|| match self.backend {
+            Backend::Json | Backend::Memory => PathBuf::from(JSON_STORE_FILE),
+            Backend::Sqlite => PathBuf::from(SQLITE_STORE_FILE),
+        })
+    }
+
+    /// The banking ledger lives beside the archive; a volatile vault keeps it in memory.
+    pub fn ledger_path(&self) -> Option<PathBuf> {
+        match self.backend {
+            Backend::Memory => None,
+            Backend::Json | Backend::Sqlite => Some(self.store_path().with_file_name(LEDGER_FILE)),
+        }
+    }
+}
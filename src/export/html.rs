@@ -0,0 +1,348 @@
+use crate::geometry::twelve_hour;
+use crate::markdown::{escape, render_note, WikiLinks, THEME_CSS};
+use crate::note::{coverage_level, NoteKey, Notes, TimeNote};
+use crate::store::backup::write_atomic;
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Layout on top of the gold theme, written out as `style.css`.
+const SITE_CSS: &str = include_str!("site.css");
+
+/// Filters the embedded note list on `search.html`; the only script on the site.
+const SEARCH_JS: &str = r#"
+const input = document.getElementById('query');
+const results = document.getElementById('results');
+const escape = s => s.replace(/[&<>"]/g, c => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;' }[c]));
+function run() {
+  const words = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+  results.innerHTML = '';
+  if (!words.length) return;
+  for (const note of NOTES) {
+    const text = note.text.toLowerCase();
+    if (!words.every(w => text.includes(w))) continue;
+    const at = text.indexOf(words[0]);
+    const start = Math.max(0, at - 60);
+    const snippet = (start > 0 ? '… ' : '') + escape(note.text.slice(start, at + 140)).replace(/\s+/g, ' ');
+    results.insertAdjacentHTML('beforeend', `<li><a href="${note.url}">${escape(note.label)}</a><div class="muted">${snippet}</div></li>`);
+  }
+}
+input.addEventListener('input', run);
+input.value = new URLSearchParams(location.search).get('q') || '';
+run();
+"#;
+
+/// Writes a self-contained journal site into `root`: an index of months,
+/// a calendar page per month, a page per day, a tag page and a search page.
+/// Everything links relatively and loads nothing from the network, so the
+/// folder can be opened from disk or published as is. Returns the number of
+/// pages written.
+pub fn write_site(root: &Path, notes: &Notes) -> io::Result<usize> {
+    let mut days: BTreeMap<NaiveDate, Vec<(NoteKey, &TimeNote)>> = BTreeMap::new();
+    for (key, note) in notes {
+        days.entry(key.date).or_default().push((*key, note));
+    }
+    let mut months: BTreeMap<NaiveDate, Vec<NaiveDate>> = BTreeMap::new();
+    for date in days.keys() {
+        months.entry(date.with_day(1).expect("every month has a first")).or_default().push(*date);
+    }
+
+    fs::create_dir_all(root)?;
+    write_atomic(&root.join("style.css"), format!("{THEME_CSS}\n{SITE_CSS}").as_bytes())?;
+    let mut pages = 0;
+    let mut write = |path: &str, html: String| -> io::Result<()> {
+        let path = root.join(path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        pages += 1;
+        write_atomic(&path, html.as_bytes())
+    };
+
+    let dates: Vec<NaiveDate> = days.keys().copied().collect();
+    for (i, (date, hours)) in days.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|i| dates[i]);
+        write(&day_path(*date), day_page(*date, hours, prev, dates.get(i + 1).copied(), notes))?;
+    }
+    for (month, dates) in &months {
+        write(&month_path(*month), month_page(*month, dates, &days))?;
+    }
+    write("index.html", index_page(&months, &days))?;
+    write("tags.html", tags_page(notes))?;
+    write("search.html", search_page(notes)?)?;
+    Ok(pages)
+}
+
+fn day_path(date: NaiveDate) -> String {
+    date.format("%Y/%m/%d.html").to_string()
+}
+
+fn month_path(month: NaiveDate) -> String {
+    month.format("%Y/%m/index.html").to_string()
+}
+
+fn hour_label(key: NoteKey) -> String {
+    let (h, meridiem) = twelve_hour(key.hour);
+    format!("{h} {meridiem}")
+}
+
+/// Link to the hour `key` from a page `depth` folders below the site root.
+fn hour_link(key: NoteKey, depth: usize) -> String {
+    format!("{}{}#h{:02}", "../".repeat(depth), day_path(key.date), key.hour)
+}
+
+fn page(title: &str, depth: usize, body: &str) -> String {
+    let up = "../".repeat(depth);
+    let title = escape(title);
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title} · Chronos Aeternum</title>\n<link rel=\"stylesheet\" href=\"{up}style.css\">\n</head>\n<body>\n\
+         <header class=\"site\">\n<a class=\"brand gold-text\" href=\"{up}index.html\">Chronos Plantacerium</a>\n\
+         <nav><a href=\"{up}index.html\">Archive</a><a href=\"{up}tags.html\">Tags</a><a href=\"{up}search.html\">Search</a></nav>\n\
+         </header>\n<main>\n<h1 class=\"page gold-text\">{title}</h1>\n{body}</main>\n</body>\n</html>\n"
+    )
+}
+
+fn day_page(date: NaiveDate, hours: &[(NoteKey, &TimeNote)], prev: Option<NaiveDate>, next: Option<NaiveDate>, notes: &Notes) -> String {
+    let link = |date: Option<NaiveDate>, text: &str| {
+        date.map(|d| format!("<a href=\"../../{}\">{text}</a>", day_path(d))).unwrap_or_else(|| "<span></span>".into())
+    };
+    let mut body = format!(
+        "<div class=\"pager\">{}<a href=\"index.html\">{}</a>{}</div>\n",
+        link(prev, "‹ Previous day"),
+        date.format("%B %Y"),
+        link(next, "Next day ›")
+    );
+    // Wiki-links lead to hours on the site; links to hours it lacks stay plain text
+    let href = |key: NoteKey| notes.contains_key(&key).then(|| hour_link(key, 2));
+    let links = WikiLinks { date, href: &href };
+    for (key, note) in hours {
+        let badge = if note.is_locked { "<span class=\"badge banked\">Banked</span>" } else { "<span class=\"badge\">Planned</span>" };
+        let _ = write!(
+            body,
+            "<section class=\"hour\" id=\"h{:02}\">\n<h2>{}{badge}</h2>\n<div class=\"markdown-body\">\n{}</div>\n</section>\n",
+            key.hour,
+            hour_label(*key),
+            render_note(&note.content, &links)
+        );
+    }
+    page(&date.format("%A %d %B %Y").to_string(), 2, &body)
+}
+
+fn month_page(month: NaiveDate, dates: &[NaiveDate], days: &BTreeMap<NaiveDate, Vec<(NoteKey, &TimeNote)>>) -> String {
+    let mut body = String::from("<div class=\"calendar\">\n");
+    for weekday in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"] {
+        let _ = writeln!(body, "<div class=\"weekday\">{weekday}</div>");
+    }
+    for _ in 0..month.weekday().num_days_from_monday() {
+        body.push_str("<div></div>\n");
+    }
+    for date in month.iter_days().take_while(|d| d.month() == month.month()) {
+        let hours = days.get(&date).map_or(0, Vec::len);
+        let cell = format!("<div>{}</div>{}", date.day(), if hours > 0 { format!("<div class=\"count\">{hours}h</div>") } else { String::new() });
+        if hours > 0 {
+            let _ = writeln!(body, "<a class=\"day heat-{}\" href=\"{}\">{cell}</a>", coverage_level(hours), date.format("%d.html"));
+        } else {
+            let _ = writeln!(body, "<div class=\"day heat-0\">{cell}</div>");
+        }
+    }
+    body.push_str("</div>\n<ul class=\"entries\">\n");
+    for date in dates {
+        for (key, note) in &days[date] {
+            let _ = writeln!(
+                body,
+                "<li><a href=\"{}\">{} · {}</a> <span class=\"muted\">{}</span></li>",
+                hour_link(*key, 2),
+                date.format("%a %d"),
+                hour_label(*key),
+                escape(first_line(&note.content))
+            );
+        }
+    }
+    body.push_str("</ul>\n");
+    page(&month.format("%B %Y").to_string(), 2, &body)
+}
+
+fn index_page(months: &BTreeMap<NaiveDate, Vec<NaiveDate>>, days: &BTreeMap<NaiveDate, Vec<(NoteKey, &TimeNote)>>) -> String {
+    let mut body = String::new();
+    let mut year = None;
+    for (month, dates) in months.iter().rev() {
+        if year != Some(month.year()) {
+            if year.is_some() {
+                body.push_str("</ul>\n");
+            }
+            year = Some(month.year());
+            let _ = writeln!(body, "<h2 class=\"gold-text\">{}</h2>\n<ul class=\"entries\">", month.year());
+        }
+        let hours: usize = dates.iter().map(|d| days[d].len()).sum();
+        let _ = writeln!(
+            body,
+            "<li><a href=\"{}\">{}</a> <span class=\"muted\">{} days · {hours} hours</span></li>",
+            month_path(*month),
+            month.format("%B"),
+            dates.len()
+        );
+    }
+    if year.is_some() {
+        body.push_str("</ul>\n");
+    } else {
+        body.push_str("<p class=\"muted\">The archive is empty.</p>\n");
+    }
+    page("The Archive", 0, &body)
+}
+
+fn tags_page(notes: &Notes) -> String {
+    let mut tags: BTreeMap<String, BTreeSet<NoteKey>> = BTreeMap::new();
+    for (key, note) in notes {
+        for tag in tags_in(&note.content) {
+            tags.entry(tag).or_default().insert(*key);
+        }
+    }
+    let mut body = String::from("<p class=\"tags\">\n");
+    for (tag, keys) in &tags {
+        let _ = writeln!(body, "<a href=\"#tag-{0}\">#{0} <span class=\"muted\">{1}</span></a>", escape(tag), keys.len());
+    }
+    body.push_str("</p>\n");
+    for (tag, keys) in &tags {
+        let _ = writeln!(body, "<h2 id=\"tag-{0}\" class=\"gold-text\">#{0}</h2>\n<ul class=\"entries\">", escape(tag));
+        for key in keys.iter().rev() {
+            let _ = writeln!(body, "<li><a href=\"{}\">{} · {}</a></li>", hour_link(*key, 0), key.date.format("%a %d %b %Y"), hour_label(*key));
+        }
+        body.push_str("</ul>\n");
+    }
+    if tags.is_empty() {
+        body.push_str("<p class=\"muted\">No observation carries a #tag yet.</p>\n");
+    }
+    page("Tags", 0, &body)
+}
+
+fn search_page(notes: &Notes) -> io::Result<String> {
+    #[derive(Serialize)]
+    struct Entry {
+        url: String,
+        label: String,
+        text: String,
+    }
+    let entries: Vec<Entry> = notes
+        .iter()
+        .rev()
+        .map(|(key, note)| Entry {
+            url: hour_link(*key, 0),
+            label: format!("{} · {}", key.date.format("%a %d %b %Y"), hour_label(*key)),
+            text: note.content.clone(),
+        })
+        .collect();
+    // `</` would end the script element early
+    let data = serde_json::to_string(&entries)?.replace("</", "<\\/");
+    let body = format!(
+        "<input id=\"query\" type=\"search\" placeholder=\"Search the archive\" autofocus>\n\
+         <ul id=\"results\" class=\"entries\"></ul>\n\
+         <noscript><p class=\"muted\">Searching needs JavaScript; the tag and month pages work without it.</p></noscript>\n\
+         <script>\nconst NOTES = {data};\n{SEARCH_JS}</script>\n"
+    );
+    Ok(page("Search", 0, &body))
+}
+
+/// `#tags` in `text`: a `#` at the start of a word followed by letters,
+/// digits, `-` or `_`. Markdown headings (`# Title`) have a space and never match.
+fn tags_in(text: &str) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        if c == '#' && prev.is_whitespace() {
+            let tag: String = text[i + 1..].chars().take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_').collect();
+            if tag.chars().any(char::is_alphabetic) {
+                tags.insert(tag.to_lowercase());
+            }
+        }
+        prev = c;
+    }
+    tags
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn key(s: &str) -> NoteKey {
+        NoteKey::parse(s).unwrap()
+    }
+
+    fn note(content: &str, is_locked: bool) -> TimeNote {
+        TimeNote { content: content.into(), is_locked }
+    }
+
+    /// Every local `href` on the page at `path`, resolved against its folder
+    /// with any `#fragment` dropped.
+    fn local_links(path: &Path) -> Vec<PathBuf> {
+        let html = fs::read_to_string(path).unwrap();
+        html.split("href=\"")
+            .skip(1)
+            .map(|rest| &rest[..rest.find('"').unwrap()])
+            // Fragments, and the search script's template
+            .filter(|href| !href.starts_with(['#', '$']))
+            .map(|href| path.parent().unwrap().join(href.split('#').next().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn every_page_links_to_pages_that_exist() {
+        let notes = Notes::from([
+            (key("2026-09-30-23"), note("End of #September", true)),
+            (key("2026-10-17-09"), note("Dawn by the river #walk", true)),
+            (key("2026-10-17-21"), note("See [[today 9]] and [[2026-09-30-23]], not [[2026-10-01 8]] #Walk", false)),
+            (key("2027-01-02-13"), note("Next year", false)),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        // Three days, three months, the index, tags and search
+        assert_eq!(write_site(dir.path(), &notes).unwrap(), 9);
+
+        let pages = ["index.html", "tags.html", "search.html", "2026/10/17.html", "2026/10/index.html", "2026/09/30.html", "2027/01/index.html"];
+        for page in pages {
+            let links = local_links(&dir.path().join(page));
+            assert!(!links.is_empty(), "{page}");
+            for link in links {
+                assert!(link.is_file(), "{page} links to missing {}", link.display());
+            }
+        }
+
+        let day = fs::read_to_string(dir.path().join("2026/10/17.html")).unwrap();
+        assert!(day.contains("href=\"../../style.css\""));
+        assert!(day.contains("href=\"../../2026/09/30.html\">‹ Previous day"));
+        assert!(day.contains("href=\"../../2027/01/02.html\">Next day ›"));
+        assert!(day.contains("href=\"../../2026/10/17.html#h09\""), "wiki-link to an hour on the site");
+        assert!(day.contains("<span class=\"wiki-link\">2026-10-01 8</span>"), "wiki-link to an hour the site lacks");
+        let month = fs::read_to_string(dir.path().join("2026/10/index.html")).unwrap();
+        assert!(month.contains("href=\"17.html\"") && month.contains("href=\"../../2026/10/17.html#h21\""));
+        let tags = fs::read_to_string(dir.path().join("tags.html")).unwrap();
+        assert!(tags.contains("id=\"tag-walk\"") && tags.contains("href=\"2026/10/17.html#h21\""));
+    }
+
+    #[test]
+    fn embedded_search_data_cannot_end_its_script() {
+        let notes = Notes::from([(key("2026-10-17-09"), note("</script><script>alert(1)</script>", false))]);
+        let html = search_page(&notes).unwrap();
+        assert_eq!(html.matches("</script>").count(), 1, "only the page's own closing tag");
+        assert!(html.contains(r#"<\/script><script>alert(1)<\/script>"#));
+        let data = html.split_once("const NOTES = ").unwrap().1.split_once(";\n").unwrap().0;
+        let entries: serde_json::Value = serde_json::from_str(data).unwrap();
+        assert_eq!(entries[0]["text"], "</script><script>alert(1)</script>");
+        assert_eq!(entries[0]["url"], "2026/10/17.html#h09");
+    }
+
+    #[test]
+    fn finds_tags_but_not_headings() {
+        let tags = tags_in("# Heading\n#river and #Dawn_walk, #2026 #x-1 mid#word (#paren) #");
+        assert_eq!(tags.into_iter().collect::<Vec<_>>(), ["dawn_walk", "river", "x-1"]);
+    }
+}
@@ -0,0 +1,335 @@
+use crate::note::{NoteKey, Notes, TimeNote};
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+/// Custom property carrying an hour's banked state.
+const BANKED_PROPERTY: &str = "X-CHRONOS-BANKED";
+
+/// Longest content line, in octets, before it is folded (RFC 5545 §3.1).
+const LINE_LIMIT: usize = 75;
+
+/// Renders every note as a one-hour VEVENT starting at its hour, in floating
+/// local time. The Markdown goes into DESCRIPTION, its first line into
+/// SUMMARY, and the banked state into `X-CHRONOS-BANKED`.
+pub fn write_calendar(notes: &Notes, stamp: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    let mut line = |text: String| fold(&mut out, &text);
+    line("BEGIN:VCALENDAR".into());
+    line("VERSION:2.0".into());
+    line("PRODID:-//Plantacerium//Chronos Aeternum//EN".into());
+    line("CALSCALE:GREGORIAN".into());
+    for (key, note) in notes {
+        line("BEGIN:VEVENT".into());
+        line(format!("UID:{key}@chronos-aeternum"));
+        line(format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")));
+        line(format!("DTSTART:{}", key.start().format("%Y%m%dT%H%M%S")));
+        line("DURATION:PT1H".into());
+        line(format!("SUMMARY:{}", escape(summary(&note.content))));
+        line(format!("DESCRIPTION:{}", escape(&note.content)));
+        line(format!("{BANKED_PROPERTY}:{}", if note.is_locked { "TRUE" } else { "FALSE" }));
+        line("END:VEVENT".into());
+    }
+    line("END:VCALENDAR".into());
+    out
+}
+
+/// Reads every VEVENT into a planned note for the local hour it starts in.
+/// Events exported from here come back with their Markdown as it was; any
+/// other event becomes a note headed by its SUMMARY (and its times, if it is
+/// not the whole hour). Events sharing an hour are joined. A calendar cannot
+/// bank an hour, so one marked banked is read as planned and reported.
+/// Returns the notes and one line per event skipped or read as planned.
+pub fn read_calendar(text: &str) -> (Notes, Vec<String>) {
+    let mut notes: Notes = Notes::new();
+    let mut notices = Vec::new();
+    let mut event: Option<BTreeMap<String, (String, String)>> = None;
+    for line in unfold(text) {
+        let Some((name, params, value)) = split_property(&line) else { continue };
+        match (name.as_str(), value.as_str()) {
+            ("BEGIN", "VEVENT") => event = Some(BTreeMap::new()),
+            ("END", "VEVENT") => {
+                let Some(props) = event.take() else { continue };
+                match event_note(&props) {
+                    Ok(Some((key, content))) => {
+                        if props.get(BANKED_PROPERTY).is_some_and(|(_, v)| v.eq_ignore_ascii_case("TRUE")) {
+                            notices.push(format!("{key}: banked in the calendar, imported as planned"));
+                        }
+                        match notes.get_mut(&key) {
+                            Some(existing) => existing.content = format!("{}\n\n---\n\n{content}", existing.content),
+                            None => {
+                                notes.insert(key, TimeNote { content, is_locked: false });
+                            }
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        let summary = props.get("SUMMARY").map(|(_, v)| unescape(v)).unwrap_or_default();
+                        notices.push(format!("skipped event `{summary}`: {e}"));
+                    }
+                }
+            }
+            _ => {
+                if let Some(props) = event.as_mut() {
+                    props.entry(name).or_insert((params, value));
+                }
+            }
+        }
+    }
+    (notes, notices)
+}
+
+/// The hour an event falls in and the Markdown it becomes there, or `None`
+/// for a cancelled event.
+fn event_note(props: &BTreeMap<String, (String, String)>) -> Result<Option<(NoteKey, String)>, String> {
+    if props.get("STATUS").is_some_and(|(_, v)| v.eq_ignore_ascii_case("CANCELLED")) {
+        return Ok(None);
+    }
+    let (params, value) = props.get("DTSTART").ok_or("no start time")?;
+    let start = parse_time(params, value)?;
+    let key = NoteKey::new(start.date(), start.hour()).expect("hour of a valid time");
+    let description = props.get("DESCRIPTION").map(|(_, v)| unescape(v)).unwrap_or_default();
+
+    // Our own export: the description is the note, verbatim
+    if props.contains_key(BANKED_PROPERTY) {
+        if description.trim().is_empty() {
+            return Err("empty observation".into());
+        }
+        return Ok(Some((key, description)));
+    }
+
+    let mut heading = props.get("SUMMARY").map(|(_, v)| unescape(v)).unwrap_or_else(|| "Event".into());
+    let end = match (props.get("DTEND"), props.get("DURATION")) {
+        (Some((params, value)), _) => Some(parse_time(params, value)?),
+        (None, Some((_, value))) => parse_duration(value).map(|d| start + d),
+        (None, None) => None,
+    };
+    if start.minute() != 0 || end.is_some_and(|end| end != start + TimeDelta::hours(1)) {
+        heading.push_str(&format!(" ({}", start.format("%H:%M")));
+        if let Some(end) = end {
+            heading.push_str(&format!("–{}", end.format("%H:%M")));
+        }
+        heading.push(')');
+    }
+    let mut content = format!("**{heading}**");
+    if let Some((_, location)) = props.get("LOCATION") {
+        content.push_str(&format!("\n\n{}", unescape(location)));
+    }
+    if !description.trim().is_empty() {
+        content.push_str(&format!("\n\n{}", description.trim_end()));
+    }
+    Ok(Some((key, content)))
+}
+
+/// A DATE-TIME as local wall-clock time: floating times as written, UTC
+/// (`Z`) and `TZID` times converted to the local zone.
+fn parse_time(params: &str, value: &str) -> Result<NaiveDateTime, String> {
+    if params.split(';').any(|p| p.eq_ignore_ascii_case("VALUE=DATE")) || value.len() == 8 {
+        return Err("all-day event has no hour".into());
+    }
+    let invalid = || format!("unreadable start time `{value}`");
+    if let Some(utc) = value.strip_suffix('Z') {
+        let time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+        return Ok(Utc.from_utc_datetime(&time).with_timezone(&Local).naive_local());
+    }
+    let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    let tzid = params.split(';').find_map(|p| p.strip_prefix("TZID=")).map(|id| id.trim_matches('"'));
+    match tzid {
+        None => Ok(time),
+        Some(id) => {
+            let tz: Tz = id.parse().map_err(|_| format!("unknown time zone `{id}`"))?;
+            let zoned = tz.from_local_datetime(&time).earliest().ok_or_else(|| format!("{value} does not exist in {id}"))?;
+            Ok(zoned.with_timezone(&Local).naive_local())
+        }
+    }
+}
+
+/// A `PT1H30M`-style duration; weeks and days are accepted, anything else is `None`.
+fn parse_duration(value: &str) -> Option<TimeDelta> {
+    let mut rest = value.strip_prefix('+').unwrap_or(value).strip_prefix('P')?;
+    let mut total = TimeDelta::zero();
+    let mut in_time = false;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('T') {
+            in_time = true;
+            rest = after;
+            continue;
+        }
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let n: i64 = rest[..digits].parse().ok()?;
+        total += match (rest.as_bytes()[digits], in_time) {
+            (b'W', false) => TimeDelta::weeks(n),
+            (b'D', false) => TimeDelta::days(n),
+            (b'H', true) => TimeDelta::hours(n),
+            (b'M', true) => TimeDelta::minutes(n),
+            (b'S', true) => TimeDelta::seconds(n),
+            _ => return None,
+        };
+        rest = &rest[digits + 1..];
+    }
+    Some(total)
+}
+
+fn summary(content: &str) -> &str {
+    let line = content.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("");
+    let line = line.trim_start_matches('#').trim();
+    if line.is_empty() { "Observation" } else { line }
+}
+
+/// Appends `line`, folded so no physical line exceeds the octet limit.
+fn fold(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// Joins folded lines back together.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Splits `NAME;PARAM=x:value` into its upper-cased name, parameters and value.
+fn split_property(line: &str) -> Option<(String, String, String)> {
+    // The first colon outside a quoted parameter ends the name and parameters
+    let mut quoted = false;
+    let colon = line.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            quoted = !quoted;
+        }
+        c == ':' && !quoted
+    })?.0;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+    Some((name.to_ascii_uppercase(), params.to_string(), value.to_string()))
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn key(s: &str) -> NoteKey {
+        NoteKey::parse(s).unwrap()
+    }
+
+    fn calendar(events: &[&str]) -> String {
+        let events: Vec<String> = events.iter().map(|e| format!("BEGIN:VEVENT\r\n{e}\r\nEND:VEVENT\r\n")).collect();
+        format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n", events.concat())
+    }
+
+    /// The local hour a time in `tz` falls in.
+    fn local_key(tz: Tz, wall: &str) -> NoteKey {
+        let wall = NaiveDateTime::parse_from_str(wall, "%Y%m%dT%H%M%S").unwrap();
+        let local = tz.from_local_datetime(&wall).unwrap().with_timezone(&Local).naive_local();
+        NoteKey::new(local.date(), local.hour()).unwrap()
+    }
+
+    #[test]
+    fn folds_at_75_octets_without_splitting_characters() {
+        let line = format!("DESCRIPTION:{}", "é€".repeat(40));
+        let mut out = String::new();
+        fold(&mut out, &line);
+        let physical: Vec<&str> = out.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(physical.len() > 1);
+        assert!(physical.iter().all(|l| l.len() <= LINE_LIMIT), "{physical:?}");
+        assert!(physical[1..].iter().all(|l| l.starts_with(' ')));
+        assert_eq!(unfold(&out), [line, String::new()]);
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        let text = "a;b,c\\d\ne";
+        assert_eq!(escape(text), "a\\;b\\,c\\\\d\\ne");
+        assert_eq!(unescape(&escape(text)), text);
+        assert_eq!(unescape("x\\Ny\\"), "x\ny\\");
+    }
+
+    #[test]
+    fn round_trips_its_own_export_as_planned() {
+        let notes = Notes::from([
+            (key("2026-10-17-09"), TimeNote { content: "# Dawn; gold, light\n\nA \\ path\n".repeat(5), is_locked: true }),
+            (key("2026-10-17-23"), TimeNote { content: "Ünïcode — 時間 ".repeat(12), is_locked: false }),
+        ]);
+        let text = write_calendar(&notes, Utc::now());
+        let (read, notices) = read_calendar(&text);
+        assert_eq!(notices, ["2026-10-17-09: banked in the calendar, imported as planned"]);
+        let planned: Notes = notes.into_iter().map(|(key, note)| (key, TimeNote { is_locked: false, ..note })).collect();
+        assert_eq!(read, planned);
+    }
+
+    #[test]
+    fn reads_zoned_and_utc_times_into_local_hours() {
+        let text = calendar(&[
+            "SUMMARY:Call\r\nDTSTART;TZID=America/New_York:20261017T090000\r\nDTEND;TZID=America/New_York:20261017T100000",
+            "SUMMARY:Flight\r\nDTSTART:20261017T231500Z\r\nDURATION:PT2H",
+            "SUMMARY:Cancelled\r\nSTATUS:CANCELLED\r\nDTSTART:20261017T120000",
+            "SUMMARY:Holiday\r\nDTSTART;VALUE=DATE:20261017",
+            "SUMMARY:Elsewhere\r\nDTSTART;TZID=Mars/Olympus:20261017T090000",
+        ]);
+        let (notes, notices) = read_calendar(&text);
+        let call = local_key(chrono_tz::America::New_York, "20261017T090000");
+        let flight = local_key(chrono_tz::UTC, "20261017T231500");
+        assert_eq!(notes.keys().copied().collect::<BTreeSet<_>>(), BTreeSet::from([call, flight]));
+        // Zones off the local hour by a fraction also note the event's times
+        assert!(notes[&call].content.starts_with("**Call"), "{}", notes[&call].content);
+        assert!(notes[&flight].content.starts_with("**Flight ("), "{}", notes[&flight].content);
+        assert!(!notes[&flight].is_locked);
+        assert_eq!(notices, ["skipped event `Holiday`: all-day event has no hour", "skipped event `Elsewhere`: unknown time zone `Mars/Olympus`"]);
+    }
+
+    #[test]
+    fn reads_durations() {
+        assert_eq!(parse_duration("PT1H30M"), Some(TimeDelta::minutes(90)));
+        assert_eq!(parse_duration("+P1W2DT3S"), Some(TimeDelta::days(9) + TimeDelta::seconds(3)));
+        for malformed in ["", "1H", "PT1X", "P1H", "PT1D", "PTH", "PT-1H"] {
+            assert_eq!(parse_duration(malformed), None, "{malformed}");
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::note::{NoteKey, Notes, TimeNote};
+use crate::store::backup::write_atomic;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Writes one `YYYY/MM/DD.md` file per day under `root`, each hour under a
+/// `## HH:00` heading. Front matter lists the day's hours and which of them
+/// are banked, so the tree reads back exactly. Returns the number of files.
+pub fn write_tree(root: &Path, notes: &Notes) -> io::Result<usize> {
+    let mut days: BTreeMap<NaiveDate, Vec<(NoteKey, &TimeNote)>> = BTreeMap::new();
+    for (key, note) in notes {
+        days.entry(key.date).or_default().push((*key, note));
+    }
+    for (date, hours) in &days {
+        let dir = root.join(date.format("%Y").to_string()).join(date.format("%m").to_string());
+        fs::create_dir_all(&dir)?;
+        write_atomic(&dir.join(date.format("%d.md").to_string()), day_file(*date, hours).as_bytes())?;
+    }
+    Ok(days.len())
+}
+
+fn day_file(date: NaiveDate, hours: &[(NoteKey, &TimeNote)]) -> String {
+    let listed: Vec<String> = hours.iter().map(|(key, _)| key.hour.to_string()).collect();
+    let banked: Vec<String> = hours.iter().filter(|(_, note)| note.is_locked).map(|(key, _)| key.hour.to_string()).collect();
+    let mut out = String::new();
+    let _ = writeln!(out, "---\ndate: {}", date.format("%Y-%m-%d"));
+    let _ = writeln!(out, "hours: [{}]", listed.join(", "));
+    let _ = writeln!(out, "banked: [{}]\n---", banked.join(", "));
+    for (key, note) in hours {
+        let _ = write!(out, "\n## {:02}:00\n\n{}\n", key.hour, shift_headings(note.content.trim_end()));
+    }
+    out
+}
+
+/// Reads a tree written by [`write_tree`] back into notes. Day files edited
+/// by hand may drop the front matter; every `## HH:00` heading then starts
+/// an hour and nothing is banked. Returns the notes and one line per file
+/// that could not be read.
+pub fn read_tree(root: &Path) -> io::Result<(Notes, Vec<String>)> {
+    let mut notes = Notes::new();
+    let mut problems = Vec::new();
+    for year in numbered(root, 4)? {
+        for month in numbered(&year, 2)? {
+            for day in fs::read_dir(&month)? {
+                let path = day?.path();
+                let Some(name) = path.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".md")) else { continue };
+                let stamp = format!("{}-{}-{name}", file_name(&year), file_name(&month));
+                let Ok(date) = NaiveDate::parse_from_str(&stamp, "%Y-%m-%d") else {
+                    problems.push(format!("{}: not a calendar day", path.display()));
+                    continue;
+                };
+                match parse_day(date, &fs::read_to_string(&path)?) {
+                    Ok(day) => notes.extend(day),
+                    Err(e) => problems.push(format!("{}: {e}", path.display())),
+                }
+            }
+        }
+    }
+    Ok((notes, problems))
+}
+
+/// Subdirectories of `dir` named with exactly `digits` digits, as `2026` or `10`.
+fn numbered(dir: &Path, digits: usize) -> io::Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = file_name(&path);
+        if path.is_dir() && name.len() == digits && name.bytes().all(|b| b.is_ascii_digit()) {
+            dirs.push(path);
+        }
+    }
+    Ok(dirs)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn parse_day(date: NaiveDate, text: &str) -> Result<Notes, String> {
+    let (front, body) = split_front_matter(text);
+    let mut hours: Option<Vec<u32>> = None;
+    let mut banked = Vec::new();
+    for line in front.lines() {
+        let Some((field, value)) = line.split_once(':') else { continue };
+        match field.trim() {
+            "date" if value.trim() != date.format("%Y-%m-%d").to_string() => {
+                return Err(format!("front matter dates it {}", value.trim()));
+            }
+            "hours" => hours = Some(hour_list(value)?),
+            "banked" => banked = hour_list(value)?,
+            _ => {}
+        }
+    }
+
+    // Split at hour headings; with front matter only at the listed hours, in order
+    let mut expected = hours.as_deref().unwrap_or(&[]).iter().copied().peekable();
+    let mut sections: Vec<(u32, Vec<&str>)> = Vec::new();
+    for line in body.lines() {
+        let heading = hour_heading(line).filter(|&h| match (&hours, sections.last()) {
+            (Some(_), _) => expected.peek() == Some(&h),
+            (None, Some((prev, _))) => h > *prev,
+            (None, None) => true,
+        });
+        match heading {
+            Some(h) => {
+                expected.next();
+                sections.push((h, Vec::new()));
+            }
+            None => {
+                if let Some((_, lines)) = sections.last_mut() {
+                    lines.push(unshift_heading(line));
+                }
+            }
+        }
+    }
+    if let Some(missing) = expected.next() {
+        return Err(format!("hour {missing:02}:00 listed in the front matter but not found"));
+    }
+
+    let mut notes = Notes::new();
+    for (hour, lines) in sections {
+        let content = lines.join("\n").trim_start_matches(['\n', '\r']).trim_end().to_string();
+        if content.is_empty() {
+            continue;
+        }
+        let key = NoteKey::new(date, hour).expect("headings only match hours below 24");
+        notes.insert(key, TimeNote { content, is_locked: banked.contains(&hour) });
+    }
+    Ok(notes)
+}
+
+/// Front matter between leading `---` fences, and the text after it.
+fn split_front_matter(text: &str) -> (&str, &str) {
+    let Some(rest) = text.strip_prefix("---\n").or_else(|| text.strip_prefix("---\r\n")) else { return ("", text) };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (&rest[..offset], &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    ("", text)
+}
+
+/// Parses `[9, 21]` into hours, rejecting anything outside 0-23.
+fn hour_list(value: &str) -> Result<Vec<u32>, String> {
+    let inner = value.trim().trim_start_matches('[').trim_end_matches(']');
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|h| !h.is_empty())
+        .map(|h| h.parse().ok().filter(|h| *h < 24).ok_or_else(|| format!("`{h}` is not an hour")))
+        .collect()
+}
+
+/// Indents every line of a note that reads as an hour heading by one more
+/// space, so it cannot split the day file. Markdown renders it unchanged.
+fn shift_headings(content: &str) -> String {
+    content
+        .split('\n')
+        .map(|line| if hour_heading(line.trim_start_matches(' ')).is_some() { format!(" {line}") } else { line.to_string() })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reverses [`shift_headings`] for one line.
+fn unshift_heading(line: &str) -> &str {
+    match line.strip_prefix(' ') {
+        Some(rest) if hour_heading(rest.trim_start_matches(' ')).is_some() => rest,
+        _ => line,
+    }
+}
+
+/// The hour of a `## HH:00` heading.
+fn hour_heading(line: &str) -> Option<u32> {
+    let time = line.strip_prefix("## ")?.trim();
+    let hour = time.strip_suffix(":00")?;
+    if hour.len() != 2 || !hour.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    hour.parse().ok().filter(|h| *h < 24)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> NoteKey {
+        NoteKey::parse(s).unwrap()
+    }
+
+    fn note(content: &str, is_locked: bool) -> TimeNote {
+        TimeNote { content: content.into(), is_locked }
+    }
+
+    #[test]
+    fn tree_round_trips() {
+        let notes = Notes::from([
+            (key("2026-10-17-00"), note("Midnight", true)),
+            (key("2026-10-17-09"), note("---\ndate: 1999-01-01\nhours: [3]\nbanked: [3]\n---\n\n## 07:00 in a note\n\n- [x] kept", false)),
+            (key("2026-10-17-12"), note("Before\n\n## 21:00\n   ## 21:00\n\nAfter", false)),
+            (key("2026-10-17-21"), note("## Evening\n\n---\n\nbanked: [21]", true)),
+            (key("2027-01-02-13"), note("Next year", false)),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(write_tree(dir.path(), &notes).unwrap(), 2);
+        assert!(dir.path().join("2026/10/17.md").is_file());
+
+        let (read, problems) = read_tree(dir.path()).unwrap();
+        assert!(problems.is_empty(), "{problems:?}");
+        assert_eq!(read, notes);
+    }
+
+    #[test]
+    fn hand_written_days_need_no_front_matter() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let day = parse_day(date, "Preamble\n\n## 08:00\nWoke\n\n## 7:00\n## 06:00\nStill 08\n## 20:00\n\nSlept\n").unwrap();
+        assert_eq!(day.keys().copied().collect::<Vec<_>>(), [key("2026-10-17-08"), key("2026-10-17-20")]);
+        assert_eq!(day[&key("2026-10-17-08")], note("Woke\n\n## 7:00\n## 06:00\nStill 08", false));
+        assert!(parse_day(date, "---\ndate: 2026-10-18\n---\n").is_err());
+        assert!(parse_day(date, "---\nhours: [9]\n---\n## 10:00\nx\n").is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::ledger::Ledger;
+use crate::note::{Notes, TimeNote};
+use crate::store::{NoteStore, StoreError};
+use chrono::{DateTime, Local};
+
+pub mod html;
+pub mod ics;
+pub mod markdown;
+pub mod table;
+
+// --- Export & Import ---
+
+/// What merging an imported archive into the store did.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Imported {
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// One line per note left alone, and why.
+    pub skipped: Vec<String>,
+    /// One line per note that arrived banked but was imported as planned.
+    pub planned: Vec<String>,
+}
+
+/// Writes `notes` into `store`, then banks any that arrive locked into the
+/// ledger as of `at`. Hours already banked are never overwritten, and an
+/// hour that has not begun by `at` is imported as planned, since it cannot
+/// be banked yet.
+pub fn merge(store: &mut dyn NoteStore, ledger: &mut Ledger, notes: Notes, at: DateTime<Local>) -> Result<Imported, StoreError> {
+    let mut imported = Imported::default();
+    let mut banked = Vec::new();
+    for (key, mut note) in notes {
+        if note.is_locked && !key.has_begun(at.naive_local()) {
+            imported.planned.push(format!("{key}: banked in the import but not yet begun, imported as planned"));
+            note.is_locked = false;
+        }
+        match store.get(key)? {
+            Some(existing) if existing == note => imported.unchanged += 1,
+            Some(existing) if existing.is_locked => imported.skipped.push(format!("{key}: already banked, left as it is")),
+            existing => {
+                if existing.is_some() {
+                    imported.updated += 1;
+                } else {
+                    imported.added += 1;
+                }
+                if note.is_locked && ledger.entry(key).is_none() {
+                    banked.push((key, note.clone()));
+                }
+                store.put(key, note)?;
+            }
+        }
+    }
+    store.flush()?;
+    for (i, (key, note)) in banked.iter().enumerate() {
+        if let Err(e) = ledger.append(*key, note, at) {
+            // Leave the rest planned rather than locked without a ledger entry
+            for (key, note) in &banked[i..] {
+                store.put(*key, TimeNote { is_locked: false, ..note.clone() })?;
+            }
+            store.flush()?;
+            return Err(e);
+        }
+    }
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::note::NoteKey;
+    use crate::store::MemoryStore;
+    use chrono::TimeZone;
+
+    #[test]
+    fn merge_leaves_hours_planned_when_the_ledger_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        // The ledger's folder does not exist, so appending fails
+        let mut ledger = Ledger::open(Some(dir.path().join("missing").join("ledger.jsonl"))).unwrap();
+        let mut store = MemoryStore::default();
+        let banked = |content: &str| TimeNote { content: content.into(), is_locked: true };
+        let notes = Notes::from([
+            (NoteKey::parse("2026-10-16-09").unwrap(), banked("one")),
+            (NoteKey::parse("2026-10-16-10").unwrap(), banked("two")),
+        ]);
+
+        assert!(merge(&mut store, &mut ledger, notes, Local::now()).is_err());
+        assert!(ledger.entries().is_empty());
+        assert_eq!(store.notes().len(), 2);
+        assert!(store.notes().values().all(|note| !note.is_locked));
+    }
+
+    #[test]
+    fn merge_never_banks_an_hour_that_has_not_begun() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = Ledger::open(Some(dir.path().join("ledger.jsonl"))).unwrap();
+        let mut store = MemoryStore::default();
+        let banked = |content: &str| TimeNote { content: content.into(), is_locked: true };
+        let (past, current, future) = (
+            NoteKey::parse("2026-10-17-08").unwrap(),
+            NoteKey::parse("2026-10-17-09").unwrap(),
+            NoteKey::parse("2099-01-01-09").unwrap(),
+        );
+        let notes = Notes::from([(past, banked("past")), (current, banked("current")), (future, banked("sealed future"))]);
+        let at = Local.with_ymd_and_hms(2026, 10, 17, 9, 30, 0).unwrap();
+
+        let imported = merge(&mut store, &mut ledger, notes, at).unwrap();
+        assert_eq!(imported.added, 3);
+        assert_eq!(imported.planned, ["2099-01-01-09: banked in the import but not yet begun, imported as planned"]);
+        assert_eq!(ledger.entries().iter().map(|e| e.key).collect::<Vec<_>>(), [past, current]);
+        assert!(store.notes()[&past].is_locked && store.notes()[&current].is_locked);
+        assert_eq!(store.notes()[&future], TimeNote { content: "sealed future".into(), is_locked: false });
+    }
+}
@@ -0,0 +1,186 @@
+use crate::ledger::Ledger;
+use crate::note::{NoteKey, Notes, TimeNote};
+use chrono::TimeDelta;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io;
+
+/// Column order of the CSV header; JSON Lines objects use the same fields in
+/// the same order. Append new columns at the end, never rename or reorder.
+pub const COLUMNS: [&str; 8] = ["key", "date", "hour", "start", "end", "banked", "banked_at", "content"];
+
+/// One observation as a flat record for spreadsheets and data tools.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Row {
+    /// `YYYY-MM-DD-HH`
+    pub key: String,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    /// 0-23
+    pub hour: u32,
+    /// Local wall-clock start of the hour, `YYYY-MM-DDTHH:MM:SS`
+    pub start: String,
+    /// Local wall-clock end of the hour
+    pub end: String,
+    pub banked: bool,
+    /// RFC 3339 moment the ledger sealed the hour, if it has
+    pub banked_at: Option<String>,
+    pub content: String,
+}
+
+/// One row per note, in chronological order, with banking times taken from `ledger`.
+pub fn rows(notes: &Notes, ledger: &Ledger) -> Vec<Row> {
+    let banked_at: BTreeMap<_, _> = ledger.entries().iter().map(|e| (e.key, e.banked_at.as_str())).collect();
+    let stamp = "%Y-%m-%dT%H:%M:%S";
+    notes
+        .iter()
+        .map(|(key, note)| Row {
+            key: key.to_string(),
+            date: key.date.format("%Y-%m-%d").to_string(),
+            hour: key.hour,
+            start: key.start().format(stamp).to_string(),
+            end: (key.start() + TimeDelta::hours(1)).format(stamp).to_string(),
+            banked: note.is_locked,
+            banked_at: banked_at.get(key).filter(|_| note.is_locked).map(|at| at.to_string()),
+            content: note.content.clone(),
+        })
+        .collect()
+}
+
+/// RFC 4180 CSV: a header line, then one record per row, CRLF-terminated.
+/// Fields holding commas, quotes or line breaks are quoted.
+pub fn write_csv(rows: &[Row]) -> String {
+    let mut out = COLUMNS.join(",") + "\r\n";
+    for row in rows {
+        let fields = [
+            row.key.as_str(),
+            &row.date,
+            &row.hour.to_string(),
+            &row.start,
+            &row.end,
+            if row.banked { "true" } else { "false" },
+            row.banked_at.as_deref().unwrap_or(""),
+            &row.content,
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| quote(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// One compact JSON object per line.
+pub fn write_jsonl(rows: &[Row]) -> io::Result<String> {
+    let mut out = String::new();
+    for row in rows {
+        out.push_str(&serde_json::to_string(row)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Reads CSV written by [`write_csv`] back into notes. Columns are found by
+/// their header names, so extra or reordered columns are fine; only `key`,
+/// `banked` and `content` are needed. Returns the notes and one line per
+/// record that was skipped.
+pub fn read_csv(text: &str) -> (Notes, Vec<String>) {
+    let mut notes = Notes::new();
+    let mut problems = Vec::new();
+    let mut records = records(text).into_iter();
+    let header = records.next().unwrap_or_default();
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let (Some(key_at), Some(banked_at), Some(content_at)) = (column("key"), column("banked"), column("content")) else {
+        problems.push("header needs `key`, `banked` and `content` columns".into());
+        return (notes, problems);
+    };
+    for (line, record) in records.enumerate() {
+        let field = |at: usize| record.get(at).map(String::as_str).unwrap_or("");
+        let Some(key) = NoteKey::parse(field(key_at)) else {
+            problems.push(format!("record {}: `{}` is not a YYYY-MM-DD-HH key", line + 1, field(key_at)));
+            continue;
+        };
+        let is_locked = match field(banked_at) {
+            "true" => true,
+            "false" => false,
+            other => {
+                problems.push(format!("{key}: banked is `{other}`, not true or false"));
+                continue;
+            }
+        };
+        notes.insert(key, TimeNote { content: field(content_at).to_string(), is_locked });
+    }
+    (notes, problems)
+}
+
+/// Splits RFC 4180 text into records of unquoted fields. Line breaks inside
+/// quotes belong to the field; a trailing line break ends the last record.
+fn records(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', _) => quoted = !quoted,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\r', false) if chars.peek() == Some(&'\n') => {}
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+fn quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_fields_that_need_it_and_reads_them_back() {
+        let notes = Notes::from([
+            (NoteKey::parse("2026-10-17-09").unwrap(), TimeNote { content: "plain".into(), is_locked: true }),
+            (
+                NoteKey::parse("2026-10-17-10").unwrap(),
+                TimeNote { content: "She said \"now, here\"\r\n\n- [ ] list,\n\"\"".into(), is_locked: false },
+            ),
+        ]);
+        let csv = write_csv(&rows(&notes, &Ledger::default()));
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(lines[0], COLUMNS.join(","));
+        assert_eq!(lines[1], "2026-10-17-09,2026-10-17,9,2026-10-17T09:00:00,2026-10-17T10:00:00,true,,plain");
+        assert!(lines[2].ends_with(",false,,\"She said \"\"now, here\"\""));
+
+        let (read, problems) = read_csv(&csv);
+        assert!(problems.is_empty(), "{problems:?}");
+        assert_eq!(read, notes);
+    }
+
+    #[test]
+    fn reads_columns_by_name_and_reports_bad_records() {
+        let csv = "content,extra,banked,key\n\"a, b\",x,false,2026-10-17-09\nc,,maybe,2026-10-17-10\nd,,true,2026-10-17-24";
+        let (read, problems) = read_csv(csv);
+        assert_eq!(read.values().map(|n| n.content.as_str()).collect::<Vec<_>>(), ["a, b"]);
+        assert_eq!(problems.len(), 2, "{problems:?}");
+        assert_eq!(read_csv("key,content\n").1.len(), 1);
+    }
+}
@@ -0,0 +1,362 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, TimeZone, Timelike};
+
+// --- Dial Geometry ---
+
+/// Centre of the dial in its 800 × 800 SVG view box.
+pub const CENTER: f64 = 400.0;
+
+/// Circumference of the day-progress arc (2π × 240).
+pub const DAY_ARC: f64 = 1507.0;
+
+/// Radius of the twelve hour markers.
+pub const MARKER_RADIUS: f64 = 195.0;
+/// Radius of the five-minute numerals.
+pub const NUMERAL_RADIUS: f64 = 225.0;
+/// Radius of the orbiting minute "Spirit Dot".
+pub const SPIRIT_RADIUS: f64 = 230.0;
+/// Outer end of every tick mark.
+pub const TICK_OUTER: f64 = 242.0;
+
+const NANOS_PER_SECOND: f64 = 1_000_000_000.0;
+
+/// Angles of the three hands, in degrees clockwise from twelve o'clock,
+/// each within `0.0..360.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hands {
+    pub hour: f64,
+    pub minute: f64,
+    pub second: f64,
+}
+
+impl Hands {
+    /// Hands for the wall-clock time `t`. During a leap second (`nanosecond()`
+    /// of a billion or more) the second hand holds just short of twelve.
+    pub fn at(t: &impl Timelike) -> Self {
+        Hands {
+            hour: (t.hour() % 12) as f64 * 30.0 + (t.minute() as f64 / 2.0),
+            minute: (t.minute() as f64 + t.second() as f64 / 60.0) * 6.0,
+            second: (t.second() as f64 + sub_second(t)) * 6.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TickKind {
+    /// The four quarter-hour ticks.
+    Primary,
+    /// The remaining five-minute ticks.
+    Five,
+    Minute,
+}
+
+impl TickKind {
+    fn of(minute: u32) -> Self {
+        match minute {
+            0 | 15 | 30 | 45 => TickKind::Primary,
+            m if m % 5 == 0 => TickKind::Five,
+            _ => TickKind::Minute,
+        }
+    }
+
+    /// Where the tick starts; more significant ticks reach further in.
+    pub fn inner_radius(self) -> f64 {
+        match self {
+            TickKind::Primary => 205.0,
+            TickKind::Five => 218.0,
+            TickKind::Minute => 235.0,
+        }
+    }
+}
+
+/// One of the sixty minute ticks, from `inner` out to `outer`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tick {
+    pub minute: u32,
+    pub kind: TickKind,
+    /// The minute hand has reached or passed this tick.
+    pub passed: bool,
+    pub inner: (f64, f64),
+    pub outer: (f64, f64),
+}
+
+/// One of the twelve five-minute numerals.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Numeral {
+    pub minute: u32,
+    pub at: (f64, f64),
+    /// The current time lies in this five-minute block.
+    pub current: bool,
+}
+
+/// Everything the dial draws for one moment, in view-box coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DialLayout {
+    pub hands: Hands,
+    /// Seconds actually lived since local midnight; a DST day can run to 25 hours.
+    pub elapsed: u32,
+    /// Fraction of the local day elapsed, within `0.0..1.0`.
+    pub day_progress: f64,
+    /// Lit length of the day-progress arc.
+    pub day_arc: f64,
+    pub spirit_dot: (f64, f64),
+    pub ticks: Vec<Tick>,
+    pub numerals: Vec<Numeral>,
+    /// Hour markers 0 (twelve o'clock) to 11, clockwise.
+    pub markers: Vec<(f64, f64)>,
+}
+
+impl DialLayout {
+    pub fn at<Tz: TimeZone>(t: &DateTime<Tz>) -> Self {
+        let wall = t.time();
+        let minute = wall.minute();
+        let (elapsed, day_progress) = day_elapsed(t);
+        let minute_progress = (minute as f64 + wall.second() as f64 / 60.0) / 60.0;
+
+        let ticks = (0..60)
+            .map(|m| {
+                let kind = TickKind::of(m);
+                let angle = minute_angle(m);
+                Tick { minute: m, kind, passed: m <= minute, inner: polar(kind.inner_radius(), angle), outer: polar(TICK_OUTER, angle) }
+            })
+            .collect();
+        let numerals = (0..12)
+            .map(|i| Numeral { minute: i * 5, at: polar(NUMERAL_RADIUS, minute_angle(i * 5)), current: minute / 5 == i })
+            .collect();
+
+        DialLayout {
+            hands: Hands::at(&wall),
+            elapsed,
+            day_progress,
+            day_arc: day_progress * DAY_ARC,
+            spirit_dot: polar(SPIRIT_RADIUS, minute_progress * 360.0),
+            ticks,
+            numerals,
+            markers: (0..12).map(|h| polar(MARKER_RADIUS, marker_angle(h))).collect(),
+        }
+    }
+}
+
+/// Point `radius` out from the centre at `degrees` clockwise from twelve o'clock.
+pub fn polar(radius: f64, degrees: f64) -> (f64, f64) {
+    let angle = degrees.to_radians();
+    (CENTER + radius * angle.sin(), CENTER - radius * angle.cos())
+}
+
+/// Angle of hour marker `h` (0-11) on the twelve-hour face.
+pub fn marker_angle(h: u32) -> f64 {
+    (h % 12) as f64 * 30.0
+}
+
+/// Angle of minute `m` (0-59) on the sixty-minute track.
+pub fn minute_angle(m: u32) -> f64 {
+    (m % 60) as f64 * 6.0
+}
+
+/// `hour` (0-23) as it reads on a twelve-hour face, with its meridiem: 21 is (9, "PM").
+pub fn twelve_hour(hour: u32) -> (u32, &'static str) {
+    let h = hour % 12;
+    (if h == 0 { 12 } else { h }, if hour >= 12 { "PM" } else { "AM" })
+}
+
+/// Fraction of a second past `t.second()`, capped below one so a leap second
+/// never pushes a hand past the next mark.
+fn sub_second(t: &impl Timelike) -> f64 {
+    t.nanosecond().min(999_999_999) as f64 / NANOS_PER_SECOND
+}
+
+/// Seconds lived since the local day began and the fraction of the day that
+/// is, measured in real time so DST days are 23 or 25 hours long.
+pub fn day_elapsed<Tz: TimeZone>(t: &DateTime<Tz>) -> (u32, f64) {
+    let date = t.date_naive();
+    let wall = t.time().num_seconds_from_midnight() as f64 + sub_second(&t.time());
+    let (Some(start), Some(end)) = (start_of_day(&t.timezone(), date), date.succ_opt().and_then(|d| start_of_day(&t.timezone(), d)))
+    else {
+        return (wall as u32, wall / 86400.0);
+    };
+    let lived = t.clone() - start.clone();
+    let lived = lived.num_seconds() as f64 + lived.subsec_nanos().min(999_999_999) as f64 / NANOS_PER_SECOND;
+    let length = (end - start).num_seconds() as f64;
+    (lived as u32, (lived / length).clamp(0.0, 1.0 - f64::EPSILON))
+}
+
+/// First moment of `date` in `tz`. Usually midnight, but where a DST jump
+/// skips midnight the day begins at the first wall-clock time that exists.
+fn start_of_day<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> Option<DateTime<Tz>> {
+    (0..=24 * 4)
+        .map(|quarter| date.and_time(NaiveTime::MIN) + TimeDelta::minutes(15 * quarter))
+        .find_map(|wall| tz.from_local_datetime(&wall).earliest())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+    use chrono_tz::{America::Santiago, America::New_York, Tz};
+    use proptest::prelude::*;
+
+    const EPS: f64 = 1e-9;
+
+    fn utc(h: u32, m: u32, s: u32, nanos: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2026, 10, 17).unwrap().and_hms_nano_opt(h, m, s, nanos).unwrap().and_utc()
+    }
+
+    fn local(tz: Tz, y: i32, mo: u32, d: u32, h: u32, m: u32) -> DateTime<Tz> {
+        tz.from_local_datetime(&NaiveDate::from_ymd_opt(y, mo, d).unwrap().and_hms_opt(h, m, 0).unwrap()).earliest().unwrap()
+    }
+
+    fn close(a: (f64, f64), b: (f64, f64)) -> bool {
+        (a.0 - b.0).abs() < 1e-6 && (a.1 - b.1).abs() < 1e-6
+    }
+
+    #[test]
+    fn midnight_points_every_hand_at_twelve() {
+        let layout = DialLayout::at(&utc(0, 0, 0, 0));
+        assert_eq!(layout.hands, Hands { hour: 0.0, minute: 0.0, second: 0.0 });
+        assert_eq!(layout.elapsed, 0);
+        assert_eq!(layout.day_progress, 0.0);
+        assert_eq!(layout.day_arc, 0.0);
+        assert!(close(layout.spirit_dot, (CENTER, CENTER - SPIRIT_RADIUS)));
+    }
+
+    #[test]
+    fn noon_folds_the_hour_hand_back_to_twelve() {
+        let layout = DialLayout::at(&utc(12, 0, 0, 0));
+        assert_eq!(layout.hands.hour, 0.0);
+        assert_eq!(layout.elapsed, 43_200);
+        assert!((layout.day_progress - 0.5).abs() < EPS);
+        assert!((layout.day_arc - DAY_ARC / 2.0).abs() < EPS);
+    }
+
+    #[test]
+    fn one_second_before_midnight() {
+        let layout = DialLayout::at(&utc(23, 59, 59, 0));
+        assert_eq!(layout.hands.hour, 11.0 * 30.0 + 59.0 / 2.0);
+        assert!((layout.hands.minute - (59.0 + 59.0 / 60.0) * 6.0).abs() < EPS);
+        assert_eq!(layout.hands.second, 354.0);
+        assert!(layout.day_progress < 1.0);
+        assert!(layout.ticks.iter().all(|tick| tick.passed));
+    }
+
+    #[test]
+    fn markers_sit_at_the_quarters() {
+        let markers = DialLayout::at(&utc(9, 0, 0, 0)).markers;
+        assert_eq!(markers.len(), 12);
+        assert!(close(markers[0], (CENTER, CENTER - MARKER_RADIUS)));
+        assert!(close(markers[3], (CENTER + MARKER_RADIUS, CENTER)));
+        assert!(close(markers[6], (CENTER, CENTER + MARKER_RADIUS)));
+        assert!(close(markers[9], (CENTER - MARKER_RADIUS, CENTER)));
+    }
+
+    #[test]
+    fn ticks_reach_in_by_significance() {
+        let layout = DialLayout::at(&utc(10, 20, 0, 0));
+        assert_eq!(layout.ticks.len(), 60);
+        assert_eq!(layout.ticks[0].kind, TickKind::Primary);
+        assert_eq!(layout.ticks[45].kind, TickKind::Primary);
+        assert_eq!(layout.ticks[10].kind, TickKind::Five);
+        assert_eq!(layout.ticks[7].kind, TickKind::Minute);
+        assert!(close(layout.ticks[0].inner, (CENTER, CENTER - 205.0)));
+        assert!(close(layout.ticks[0].outer, (CENTER, CENTER - TICK_OUTER)));
+        assert!(layout.ticks[20].passed && !layout.ticks[21].passed);
+        let current: Vec<u32> = layout.numerals.iter().filter(|n| n.current).map(|n| n.minute).collect();
+        assert_eq!(current, [20]);
+    }
+
+    #[test]
+    fn leap_second_holds_the_second_hand_below_twelve() {
+        let leap = utc(23, 59, 59, 1_500_000_000);
+        let hands = Hands::at(&leap);
+        assert!(hands.second < 360.0);
+        assert!(hands.second >= Hands::at(&utc(23, 59, 59, 999_999_999)).second);
+        let layout = DialLayout::at(&leap);
+        assert!(layout.day_progress < 1.0);
+        assert!(layout.elapsed <= 86_400);
+    }
+
+    #[test]
+    fn twelve_hour_labels() {
+        assert_eq!(twelve_hour(0), (12, "AM"));
+        assert_eq!(twelve_hour(11), (11, "AM"));
+        assert_eq!(twelve_hour(12), (12, "PM"));
+        assert_eq!(twelve_hour(21), (9, "PM"));
+    }
+
+    #[test]
+    fn spring_forward_day_is_23_hours() {
+        // 2026-03-08: New York skips 02:00-03:00
+        let noon = DialLayout::at(&local(New_York, 2026, 3, 8, 12, 0));
+        assert_eq!(noon.elapsed, 11 * 3600);
+        assert!((noon.day_progress - 11.0 / 23.0).abs() < EPS);
+        // The hands still read the wall clock
+        assert_eq!(noon.hands.hour, 0.0);
+    }
+
+    #[test]
+    fn fall_back_day_is_25_hours() {
+        // 2026-11-01: New York repeats 01:00-02:00
+        let first = local(New_York, 2026, 11, 1, 1, 30);
+        let second = first + TimeDelta::hours(1);
+        assert_eq!(first.time(), second.time());
+        let (a, b) = (DialLayout::at(&first), DialLayout::at(&second));
+        assert_eq!(a.hands, b.hands);
+        assert_eq!(b.elapsed - a.elapsed, 3600);
+        assert!(b.day_progress > a.day_progress);
+        let late = DialLayout::at(&local(New_York, 2026, 11, 1, 23, 59));
+        assert!((late.day_progress - (24.0 * 60.0 + 59.0) / (25.0 * 60.0)).abs() < EPS);
+    }
+
+    #[test]
+    fn day_without_a_midnight_starts_at_the_first_real_moment() {
+        // 2022-09-11: Santiago jumped from 00:00 straight to 01:00
+        let early = DialLayout::at(&local(Santiago, 2022, 9, 11, 1, 0));
+        assert_eq!(early.elapsed, 0);
+        assert_eq!(early.day_progress, 0.0);
+    }
+
+    proptest! {
+        #[test]
+        fn hands_stay_on_the_face(secs in 0u32..86_400, nanos in 0u32..2_000_000_000) {
+            // Nanoseconds past a billion are only valid (as a leap second) at :59
+            let t = NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+                .or_else(|| NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos % 1_000_000_000))
+                .unwrap();
+            let hands = Hands::at(&t);
+            for angle in [hands.hour, hands.minute, hands.second] {
+                prop_assert!((0.0..360.0).contains(&angle));
+            }
+        }
+
+        #[test]
+        fn hands_turn_at_their_rates(secs in 0u32..86_399) {
+            let t = NaiveTime::from_num_seconds_from_midnight_opt(secs, 0).unwrap();
+            let next = t + TimeDelta::seconds(1);
+            let (a, b) = (Hands::at(&t), Hands::at(&next));
+            // Second hand: 6° a second, wrapping at the minute
+            prop_assert!(((b.second - a.second).rem_euclid(360.0) - 6.0).abs() < EPS);
+            // Minute hand: 0.1° a second, wrapping at the hour
+            prop_assert!(((b.minute - a.minute).rem_euclid(360.0) - 0.1).abs() < EPS);
+        }
+
+        #[test]
+        fn day_progress_is_monotonic_under_any_offset(secs in 0u32..86_399, offset in -14 * 3600..=14 * 3600) {
+            let tz = FixedOffset::east_opt(offset).unwrap();
+            let day = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+            let t = tz.from_local_datetime(&day.and_time(NaiveTime::from_num_seconds_from_midnight_opt(secs, 0).unwrap())).unwrap();
+            let (a, b) = (DialLayout::at(&t), DialLayout::at(&(t + TimeDelta::seconds(1))));
+            prop_assert_eq!(a.elapsed, secs);
+            prop_assert!(b.day_progress > a.day_progress);
+            prop_assert!((0.0..1.0).contains(&a.day_progress));
+            prop_assert!((a.day_arc - a.day_progress * DAY_ARC).abs() < EPS);
+        }
+
+        #[test]
+        fn everything_lies_within_the_rim(secs in 0u32..86_400) {
+            let t = utc(0, 0, 0, 0) + TimeDelta::seconds(secs.into());
+            let layout = DialLayout::at(&t);
+            let points = layout.markers.iter().chain(layout.numerals.iter().map(|n| &n.at)).chain([&layout.spirit_dot]);
+            for &(x, y) in points.chain(layout.ticks.iter().flat_map(|tick| [&tick.inner, &tick.outer])) {
+                prop_assert!(((x - CENTER).powi(2) + (y - CENTER).powi(2)).sqrt() <= TICK_OUTER + EPS);
+            }
+        }
+    }
+}
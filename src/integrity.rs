@@ -0,0 +1,43 @@
+use crate::note::{NoteKey, TimeNote};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+
+// --- Vault Integrity ---
+
+/// Outcome of re-reading the archive after it has been secured.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IntegrityReport {
+    pub notes: usize,
+    pub banked: usize,
+    pub days: usize,
+    /// Hex SHA-256 of the archive as stored.
+    pub digest: String,
+    pub problems: Vec<String>,
+}
+
+impl IntegrityReport {
+    /// Tallies decoded notes and flags any that no honest write would produce.
+    pub fn from_notes<'a>(notes: impl IntoIterator<Item = (&'a NoteKey, &'a TimeNote)>, digest: String) -> Self {
+        let mut report = IntegrityReport { digest, ..Default::default() };
+        let mut days = BTreeSet::new();
+        for (key, note) in notes {
+            report.notes += 1;
+            report.banked += usize::from(note.is_locked);
+            days.insert(key.date);
+            if note.content.contains('\0') {
+                report.problems.push(format!("{key}: observation contains NUL bytes"));
+            }
+        }
+        report.days = days.len();
+        report
+    }
+
+    pub fn passed(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Hex-encoded SHA-256 of `bytes`.
+pub fn digest(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{b:02x}")).collect()
+}
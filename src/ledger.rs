@@ -0,0 +1,331 @@
+use crate::integrity;
+use crate::note::{NoteKey, TimeNote};
+use crate::store::StoreError;
+use chrono::{DateTime, Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+// --- Banking Ledger ---
+
+/// `prev_hash` of the first entry.
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// One banked hour. `hash` commits to every other field, and through
+/// `prev_hash` to every entry before it, so no banked hour can be rewritten
+/// without breaking the chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub seq: u64,
+    pub key: NoteKey,
+    /// RFC 3339 moment the hour was banked.
+    pub banked_at: String,
+    /// SHA-256 of the note content as banked.
+    pub content_hash: String,
+    pub prev_hash: String,
+    pub hash: String,
+}
+
+impl LedgerEntry {
+    fn compute_hash(&self) -> String {
+        let preimage = format!("{}|{}|{}|{}|{}", self.seq, self.key, self.banked_at, self.content_hash, self.prev_hash);
+        integrity::digest(preimage.as_bytes())
+    }
+}
+
+/// Append-only, hash-chained record of banked experiences, one JSON entry
+/// per line in `chronos_ledger.jsonl`.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    // `None` keeps the ledger in memory only, alongside a volatile vault
+    path: Option<PathBuf>,
+    entries: Vec<LedgerEntry>,
+    // Lines that no longer parse, kept so verification can report them
+    unreadable: Vec<usize>,
+}
+
+impl Ledger {
+    pub fn open(path: Option<PathBuf>) -> Result<Self, StoreError> {
+        let mut ledger = Ledger { path, ..Default::default() };
+        let Some(path) = &ledger.path else { return Ok(ledger) };
+        let data = match fs::read_to_string(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(ledger),
+            Err(e) => return Err(e.into()),
+        };
+        ledger.load(&data);
+        Ok(ledger)
+    }
+
+    fn load(&mut self, data: &str) {
+        self.entries.clear();
+        self.unreadable.clear();
+        for (n, line) in data.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => self.entries.push(entry),
+                Err(_) => self.unreadable.push(n + 1),
+            }
+        }
+    }
+
+    /// Every entry, oldest first.
+    pub fn entries(&self) -> &[LedgerEntry] {
+        &self.entries
+    }
+
+    /// The entry that banked `key`, if it has been banked.
+    pub fn entry(&self, key: NoteKey) -> Option<&LedgerEntry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    /// Banks `note` under `key`, chaining it onto the last entry and syncing
+    /// it to disk before it is accepted. The file stays locked meanwhile and
+    /// is re-read first, so entries another process appended are chained onto
+    /// and an hour it already banked is not banked twice.
+    pub fn append(&mut self, key: NoteKey, note: &TimeNote, at: DateTime<Local>) -> Result<(), StoreError> {
+        let mut file = match &self.path {
+            Some(path) => {
+                let mut file = OpenOptions::new().create(true).read(true).append(true).open(path)?;
+                file.lock()?;
+                let mut data = String::new();
+                file.read_to_string(&mut data)?;
+                self.load(&data);
+                // Start afresh after a line left half-written by a crash, rather than gluing onto it
+                Some((file, !data.is_empty() && !data.ends_with('\n')))
+            }
+            None => None,
+        };
+        if self.entry(key).is_some() {
+            return Ok(());
+        }
+        let prev_hash = self.entries.last().map_or(GENESIS.to_string(), |e| e.hash.clone());
+        let mut entry = LedgerEntry {
+            seq: self.entries.len() as u64,
+            key,
+            banked_at: at.to_rfc3339_opts(SecondsFormat::Secs, false),
+            content_hash: integrity::digest(note.content.as_bytes()),
+            prev_hash,
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+
+        if let Some((file, torn)) = &mut file {
+            let mut line = serde_json::to_string(&entry)?;
+            line.push('\n');
+            if *torn {
+                line.insert(0, '\n');
+            }
+            file.write_all(line.as_bytes())?;
+            file.sync_all()?;
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Walks the chain and checks every banked hour against the archive,
+    /// returning one line per problem found.
+    pub fn verify<'a>(&self, notes: impl IntoIterator<Item = (&'a NoteKey, &'a TimeNote)>) -> Vec<String> {
+        let mut problems: Vec<String> = self.unreadable.iter().map(|n| format!("ledger line {n}: unreadable")).collect();
+
+        let mut prev_hash = GENESIS;
+        let mut banked = BTreeMap::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            if entry.seq != i as u64 {
+                problems.push(format!("ledger entry {i}: sequence number {} out of order", entry.seq));
+            }
+            if entry.prev_hash != prev_hash {
+                problems.push(format!("ledger entry {i} ({}): chain broken, previous entry missing or replaced", entry.key));
+            }
+            if entry.hash != entry.compute_hash() {
+                problems.push(format!("ledger entry {i} ({}): entry altered after it was written", entry.key));
+            }
+            prev_hash = &entry.hash;
+            banked.insert(entry.key, entry);
+        }
+
+        for (key, note) in notes {
+            match banked.remove(key) {
+                Some(entry) if !note.is_locked => problems.push(format!("{key}: banked on {} but no longer locked", entry.banked_at)),
+                Some(entry) if integrity::digest(note.content.as_bytes()) != entry.content_hash => {
+                    problems.push(format!("{key}: altered since it was banked on {}", entry.banked_at))
+                }
+                Some(_) => {}
+                None if note.is_locked => problems.push(format!("{key}: locked but absent from the ledger")),
+                None => {}
+            }
+        }
+        for (key, entry) in banked {
+            problems.push(format!("{key}: banked on {} but missing from the archive", entry.banked_at));
+        }
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::note::Notes;
+    use chrono::TimeZone;
+    use serde_json::Value;
+    use std::path::Path;
+
+    fn key(s: &str) -> NoteKey {
+        NoteKey::parse(s).unwrap()
+    }
+
+    fn banked(content: &str) -> TimeNote {
+        TimeNote { content: content.into(), is_locked: true }
+    }
+
+    /// Three hours banked to a ledger file, with the archive they sealed.
+    fn bank_three(path: &Path) -> Notes {
+        let notes = Notes::from([
+            (key("2026-10-17-08"), banked("dawn")),
+            (key("2026-10-17-09"), banked("morning")),
+            (key("2026-10-17-10"), banked("late morning")),
+        ]);
+        let mut ledger = Ledger::open(Some(path.to_path_buf())).unwrap();
+        let at = Local.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        for (key, note) in &notes {
+            ledger.append(*key, note, at).unwrap();
+        }
+        notes
+    }
+
+    fn lines(path: &Path) -> Vec<String> {
+        fs::read_to_string(path).unwrap().lines().map(str::to_string).collect()
+    }
+
+    fn rewrite(path: &Path, lines: &[String]) {
+        fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    fn problems(path: &Path, notes: &Notes) -> Vec<String> {
+        Ledger::open(Some(path.to_path_buf())).unwrap().verify(notes)
+    }
+
+    #[test]
+    fn an_untouched_chain_verifies() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let notes = bank_three(&path);
+        let ledger = Ledger::open(Some(path.clone())).unwrap();
+        assert_eq!(ledger.entries().len(), 3);
+        assert_eq!(ledger.entries()[0].prev_hash, GENESIS);
+        assert_eq!(ledger.entries()[2].prev_hash, ledger.entries()[1].hash);
+        assert!(ledger.verify(&notes).is_empty());
+    }
+
+    #[test]
+    fn detects_an_altered_content_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let notes = bank_three(&path);
+        let mut lines = lines(&path);
+        let mut entry: Value = serde_json::from_str(&lines[1]).unwrap();
+        entry["content_hash"] = integrity::digest(b"something else").into();
+        lines[1] = entry.to_string();
+        rewrite(&path, &lines);
+        let problems = problems(&path, &notes);
+        assert!(problems.iter().any(|p| p.contains("entry 1") && p.contains("altered after it was written")), "{problems:?}");
+        assert!(problems.iter().any(|p| p.starts_with("2026-10-17-09: altered since")), "{problems:?}");
+    }
+
+    #[test]
+    fn detects_reordered_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let notes = bank_three(&path);
+        let mut lines = lines(&path);
+        lines.swap(1, 2);
+        rewrite(&path, &lines);
+        let problems = problems(&path, &notes);
+        assert!(problems.iter().any(|p| p.contains("entry 1: sequence number 2 out of order")), "{problems:?}");
+        assert!(problems.iter().any(|p| p.contains("entry 1") && p.contains("chain broken")), "{problems:?}");
+    }
+
+    #[test]
+    fn detects_a_dropped_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let notes = bank_three(&path);
+        let mut lines = lines(&path);
+        lines.remove(1);
+        rewrite(&path, &lines);
+        let problems = problems(&path, &notes);
+        assert!(problems.iter().any(|p| p.contains("entry 1 (2026-10-17-10): chain broken")), "{problems:?}");
+        assert!(problems.contains(&"2026-10-17-09: locked but absent from the ledger".to_string()), "{problems:?}");
+    }
+
+    #[test]
+    fn detects_an_edited_banked_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let mut notes = bank_three(&path);
+        notes.get_mut(&key("2026-10-17-08")).unwrap().content = "dawn, remembered differently".into();
+        let problems = problems(&path, &notes);
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert!(problems[0].starts_with("2026-10-17-08: altered since it was banked"));
+    }
+
+    #[test]
+    fn detects_an_unlocked_banked_note() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let mut notes = bank_three(&path);
+        notes.get_mut(&key("2026-10-17-10")).unwrap().is_locked = false;
+        let problems = problems(&path, &notes);
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert!(problems[0].starts_with("2026-10-17-10: banked on") && problems[0].ends_with("no longer locked"));
+    }
+
+    #[test]
+    fn detects_a_banked_note_missing_from_the_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let mut notes = bank_three(&path);
+        notes.remove(&key("2026-10-17-09"));
+        let problems = problems(&path, &notes);
+        assert_eq!(problems.len(), 1, "{problems:?}");
+        assert!(problems[0].ends_with("missing from the archive"));
+    }
+
+    #[test]
+    fn chains_onto_entries_another_process_appended() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let at = Local.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let mut dial = Ledger::open(Some(path.clone())).unwrap();
+        let mut cli = Ledger::open(Some(path.clone())).unwrap();
+        cli.append(key("2026-10-17-08"), &banked("dawn"), at).unwrap();
+        dial.append(key("2026-10-17-09"), &banked("morning"), at).unwrap();
+
+        let notes = Notes::from([(key("2026-10-17-08"), banked("dawn")), (key("2026-10-17-09"), banked("morning"))]);
+        assert_eq!(dial.entries().len(), 2);
+        assert!(problems(&path, &notes).is_empty());
+    }
+
+    #[test]
+    fn appends_after_a_half_written_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+        let mut notes = bank_three(&path);
+        // A crash part way through writing a fourth entry
+        let mut data = fs::read_to_string(&path).unwrap();
+        data.push_str(r#"{"seq":3,"key":"2026-10-17-1"#);
+        fs::write(&path, data).unwrap();
+
+        let mut ledger = Ledger::open(Some(path.clone())).unwrap();
+        assert_eq!(ledger.entries().len(), 3);
+        let note = banked("noon");
+        ledger.append(key("2026-10-17-12"), &note, Local.with_ymd_and_hms(2026, 10, 17, 13, 0, 0).unwrap()).unwrap();
+        notes.insert(key("2026-10-17-12"), note);
+
+        let reopened = Ledger::open(Some(path.clone())).unwrap();
+        assert_eq!(reopened.entries().len(), 4);
+        assert_eq!(reopened.entries()[3].key, key("2026-10-17-12"));
+        assert_eq!(reopened.verify(&notes), ["ledger line 4: unreadable"]);
+    }
+}
@@ -0,0 +1,16 @@
+//! Chronos Aeternum's archive of hour-by-hour observations: the note model
+//! and its keys, the storage backends, the banking ledger, search, wiki-links
+//! between hours, Markdown rendering and the geometry of the dial. The
+//! desktop dial and the headless command line are thin shells over these
+//! modules.
+
+pub mod config;
+pub mod export;
+pub mod geometry;
+pub mod integrity;
+pub mod ledger;
+pub mod links;
+pub mod markdown;
+pub mod note;
+pub mod search;
+pub mod store;
@@ -0,0 +1,155 @@
+use crate::note::{NoteKey, TimeNote};
+use chrono::{NaiveDate, TimeDelta};
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+// --- Wiki-Links ---
+
+/// The hour a `[[…]]` target names: `2026-10-17 14`, the canonical
+/// `2026-10-17-14`, or a day relative to `date`, the day of the note the
+/// link is written in (`today 9`, `yesterday 21:00`, `tomorrow 7`).
+pub fn resolve(target: &str, date: NaiveDate) -> Option<NoteKey> {
+    let target = target.trim();
+    if let Some(key) = NoteKey::parse(target) {
+        return Some(key);
+    }
+    let (day, hour) = target.rsplit_once(char::is_whitespace)?;
+    let day = match day.trim().to_lowercase().as_str() {
+        "today" => date,
+        "yesterday" => date - TimeDelta::days(1),
+        "tomorrow" => date + TimeDelta::days(1),
+        day => NaiveDate::parse_from_str(day, "%Y-%m-%d").ok()?,
+    };
+    let hour = hour.strip_suffix(":00").unwrap_or(hour);
+    if hour.is_empty() || hour.len() > 2 || !hour.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    NoteKey::new(day, hour.parse().ok()?)
+}
+
+/// Every `[[target]]` in plain `text`: the byte range of the whole link and its target.
+pub fn find(text: &str) -> Vec<(Range<usize>, &str)> {
+    let mut links = Vec::new();
+    let mut from = 0;
+    while let Some(open) = text[from..].find("[[").map(|i| from + i) {
+        let inner = open + 2;
+        let Some(close) = text[inner..].find("]]").map(|i| inner + i) else { break };
+        let target = &text[inner..close];
+        if target.contains(['[', '\n']) {
+            // Only the innermost `[[` of a run like `[[[x]]` opens the link
+            from = open + 1;
+            continue;
+        }
+        links.push((open..close + 2, target));
+        from = close + 2;
+    }
+    links
+}
+
+/// Hours that `content`, written on `date`, links to. Links inside code are ignored.
+pub fn links_in(content: &str, date: NaiveDate) -> BTreeSet<NoteKey> {
+    let mut keys = BTreeSet::new();
+    let mut text = String::new();
+    let mut in_code = false;
+    for event in Parser::new_ext(content, crate::markdown::options()) {
+        match event {
+            Event::Text(t) if !in_code => {
+                text.push_str(&t);
+                continue;
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(_) | CodeBlockKind::Indented)) => in_code = true,
+            Event::End(TagEnd::CodeBlock) => in_code = false,
+            _ => {}
+        }
+        keys.extend(find(&text).into_iter().filter_map(|(_, target)| resolve(target, date)));
+        text.clear();
+    }
+    keys.extend(find(&text).into_iter().filter_map(|(_, target)| resolve(target, date)));
+    keys
+}
+
+/// Which notes link to which hours, kept current as notes are edited.
+#[derive(Clone, Debug, Default)]
+pub struct Backlinks {
+    // Linking note -> hours it links to
+    outgoing: BTreeMap<NoteKey, BTreeSet<NoteKey>>,
+    // Linked hour -> notes linking to it
+    incoming: BTreeMap<NoteKey, BTreeSet<NoteKey>>,
+}
+
+impl Backlinks {
+    pub fn build<'a>(notes: impl IntoIterator<Item = (&'a NoteKey, &'a TimeNote)>) -> Self {
+        let mut backlinks = Backlinks::default();
+        for (key, note) in notes {
+            backlinks.update(*key, Some(note));
+        }
+        backlinks
+    }
+
+    /// Re-reads the links of `key` after an edit; `None` drops them.
+    pub fn update(&mut self, key: NoteKey, note: Option<&TimeNote>) {
+        for target in self.outgoing.remove(&key).unwrap_or_default() {
+            if let Some(sources) = self.incoming.get_mut(&target) {
+                sources.remove(&key);
+                if sources.is_empty() {
+                    self.incoming.remove(&target);
+                }
+            }
+        }
+        let targets = note.map(|note| links_in(&note.content, key.date)).unwrap_or_default();
+        for target in &targets {
+            self.incoming.entry(*target).or_default().insert(key);
+        }
+        if !targets.is_empty() {
+            self.outgoing.insert(key, targets);
+        }
+    }
+
+    /// Notes linking to `key`, oldest first; a note linking to itself is left out.
+    pub fn to(&self, key: NoteKey) -> Vec<NoteKey> {
+        self.incoming.get(&key).map(|sources| sources.iter().copied().filter(|s| *s != key).collect()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> NoteKey {
+        NoteKey::parse(s).unwrap()
+    }
+
+    fn note(content: &str) -> TimeNote {
+        TimeNote { content: content.into(), is_locked: false }
+    }
+
+    #[test]
+    fn resolves_absolute_and_relative_targets() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        assert_eq!(resolve("2026-10-17 14", date), Some(key("2026-10-17-14")));
+        assert_eq!(resolve(" 2026-10-17-09 ", date), Some(key("2026-10-17-09")));
+        assert_eq!(resolve("yesterday 9", date), Some(key("2026-02-28-09")));
+        assert_eq!(resolve("Tomorrow 21:00", date), Some(key("2026-03-02-21")));
+        assert_eq!(resolve("today 0", date), Some(key("2026-03-01-00")));
+        for target in ["today 24", "yesterday", "2026-10-17", "2026-13-01 9", "someday 9", "today 9:30"] {
+            assert_eq!(resolve(target, date), None, "{target}");
+        }
+    }
+
+    #[test]
+    fn backlinks_follow_edits() {
+        let notes = crate::note::Notes::from([
+            (key("2026-10-16-09"), note("Plan for [[tomorrow 8]]")),
+            (key("2026-10-17-20"), note("Recalling [[2026-10-17 08]] and [[today 20]]")),
+        ]);
+        let mut backlinks = Backlinks::build(&notes);
+        assert_eq!(backlinks.to(key("2026-10-17-08")), [key("2026-10-16-09"), key("2026-10-17-20")]);
+        assert_eq!(backlinks.to(key("2026-10-17-20")), []);
+
+        backlinks.update(key("2026-10-16-09"), Some(&note("Plan, `[[tomorrow 8]]` dropped")));
+        assert_eq!(backlinks.to(key("2026-10-17-08")), [key("2026-10-17-20")]);
+        backlinks.update(key("2026-10-17-20"), None);
+        assert_eq!(backlinks.to(key("2026-10-17-08")), []);
+    }
+}
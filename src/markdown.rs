@@ -0,0 +1,399 @@
+use crate::links;
+use crate::note::NoteKey;
+use chrono::NaiveDate;
+use latex2mathml::{latex_to_mathml, DisplayStyle};
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use std::borrow::Cow;
+use std::sync::LazyLock;
+use syntect::html::{ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+// --- Markdown Rendering ---
+
+/// The gold theme: palette, `.gold-text` and styles for rendered
+/// observations (`.markdown-body`), shared by the dial and exported sites.
+pub const THEME_CSS: &str = include_str!("theme.css");
+
+/// Prefix given to every `id` in rendered notes (and to `#` links), so a
+/// note's footnotes can never collide with the page around it.
+const ID_PREFIX: &str = "note-";
+
+/// Grammars for highlighting fenced code, loaded on first use.
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// Prefix of the scope classes on highlighted code (`hl-keyword`, `hl-string`),
+/// coloured by the gold theme.
+const HIGHLIGHT_PREFIX: &str = "hl-";
+
+/// The MathML elements formulas are rendered with.
+const MATHML_TAGS: &[&str] = &[
+    "math", "mrow", "mi", "mn", "mo", "mtext", "mspace", "msqrt", "mroot", "mfrac", "msub", "msup", "msubsup", "munder", "mover",
+    "munderover", "mmultiscripts", "mprescripts", "none", "mtable", "mtr", "mtd", "mstyle",
+];
+
+/// GitHub-flavoured extensions (tables, task lists, strikethrough and
+/// footnotes) and `$…$` / `$$…$$` math.
+pub(crate) fn options() -> Options {
+    Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_FOOTNOTES | Options::ENABLE_MATH
+}
+
+/// Where the `[[…]]` wiki-links of a note lead.
+pub struct WikiLinks<'a> {
+    /// Day of the note; relative targets such as `[[yesterday 9]]` count from it.
+    pub date: NaiveDate,
+    /// URL of an hour's node, or `None` to show the link as plain text.
+    pub href: &'a dyn Fn(NoteKey) -> Option<String>,
+}
+
+/// Renders an observation's Markdown to HTML for the preview pane. Raw HTML
+/// in the note passes through the allow-list in [`sanitize`], so the output
+/// is safe to inject into the webview or a published page.
+///
+/// Task-list items get a `span.task-box` numbered by `data-task` in document
+/// order, the index [`toggle_task`] takes.
+pub fn render_markdown(text: &str) -> String {
+    render(text, None)
+}
+
+/// [`render_markdown`], with every `[[…]]` that names an hour turned into an
+/// `a.wiki-link` carrying the hour's key in `data-key`.
+pub fn render_note(text: &str, links: &WikiLinks) -> String {
+    render(text, Some(links))
+}
+
+fn render(text: &str, links: Option<&WikiLinks>) -> String {
+    let mut events = Vec::new();
+    // Adjacent text is gathered first, since `[` splits it into several events
+    let mut pending = String::new();
+    let mut in_code = false;
+    // A fenced block with a language tag, held back until its end to be highlighted
+    let mut fenced: Option<(CowStr, String)> = None;
+    let mut task = 0;
+    for event in Parser::new_ext(text, options()) {
+        if let Some((_, code)) = &mut fenced {
+            if let Event::Text(text) = &event {
+                code.push_str(text);
+            } else if let Event::End(TagEnd::CodeBlock) = event {
+                let (info, code) = fenced.take().expect("inside a fenced block");
+                events.extend(highlighted_block(info, code));
+            }
+            continue;
+        }
+        if let Event::Text(text) = &event
+            && !in_code
+            && links.is_some()
+        {
+            pending.push_str(text);
+            continue;
+        }
+        if let Some(links) = links {
+            push_text(&mut events, std::mem::take(&mut pending), links);
+        }
+        events.push(match event {
+            Event::TaskListMarker(checked) => {
+                let html = format!("<span class=\"task-box\" role=\"checkbox\" aria-checked=\"{checked}\" data-task=\"{task}\"></span>");
+                task += 1;
+                Event::Html(html.into())
+            }
+            Event::InlineMath(tex) => Event::Html(math(&tex, DisplayStyle::Inline).into()),
+            Event::DisplayMath(tex) => Event::Html(math(&tex, DisplayStyle::Block).into()),
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) if !info.trim().is_empty() => {
+                fenced = Some((info, String::new()));
+                continue;
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(_) | CodeBlockKind::Indented)) => {
+                in_code = true;
+                event
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code = false;
+                event
+            }
+            other => other,
+        });
+    }
+    if let Some(links) = links {
+        push_text(&mut events, pending, links);
+    }
+    let mut html_output = String::new();
+    html::push_html(&mut html_output, events.into_iter());
+    sanitize(&html_output)
+}
+
+/// A fenced block, highlighted if its language tag names a known grammar
+/// and left as plain code otherwise.
+fn highlighted_block(info: CowStr<'_>, code: String) -> Vec<Event<'_>> {
+    let lang = info.split_whitespace().next().unwrap_or_default();
+    match highlight(lang, &code) {
+        Some(spans) => {
+            vec![Event::Html(format!("<pre><code class=\"language-{}\">{spans}</code></pre>\n", escape(lang)).into())]
+        }
+        None => {
+            let tag = Tag::CodeBlock(CodeBlockKind::Fenced(info));
+            vec![Event::Start(tag), Event::Text(code.into()), Event::End(TagEnd::CodeBlock)]
+        }
+    }
+}
+
+/// A TeX formula as MathML; one that does not parse is shown as written,
+/// marked `.math-error` with the reason in its title.
+fn math(tex: &str, style: DisplayStyle) -> String {
+    let reason = match latex_to_mathml(tex.trim(), style) {
+        // Some mistakes come back inside the MathML rather than as an error
+        Ok(mathml) if !mathml.contains("[PARSE ERROR") => return mathml,
+        Ok(_) => "unreadable formula".to_string(),
+        Err(e) => e.to_string(),
+    };
+    format!("<code class=\"math-error\" title=\"{}\">{}</code>", escape(&reason), escape(tex))
+}
+
+/// `code` as HTML spans classed by syntax scope, or `None` for an unknown language.
+fn highlight(lang: &str, code: &str) -> Option<String> {
+    let syntax = SYNTAXES.find_syntax_by_token(lang)?;
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAXES, ClassStyle::SpacedPrefixed { prefix: HIGHLIGHT_PREFIX });
+    for line in LinesWithEndings::from(code) {
+        generator.parse_html_for_line_which_includes_newline(line).ok()?;
+    }
+    Some(generator.finalize())
+}
+
+/// Pushes `text` as text events, with its wiki-links as HTML between them.
+fn push_text(events: &mut Vec<Event<'_>>, text: String, links: &WikiLinks) {
+    let mut last = 0;
+    for (range, target) in links::find(&text) {
+        let Some(key) = links::resolve(target, links.date) else { continue };
+        let label = escape(target.trim());
+        let html = match (links.href)(key) {
+            Some(href) => format!("<a class=\"wiki-link\" href=\"{}\" data-key=\"{key}\">{label}</a>", escape(&href)),
+            None => format!("<span class=\"wiki-link\">{label}</span>"),
+        };
+        events.push(Event::Text(text[last..range.start].to_string().into()));
+        events.push(Event::Html(html.into()));
+        last = range.end;
+    }
+    if last < text.len() {
+        events.push(Event::Text(text[last..].to_string().into()));
+    }
+}
+
+/// Flips the `index`-th task-list item of `text` between `- [ ]` and
+/// `- [x]`, leaving every other byte alone. `None` if there is no such task.
+pub fn toggle_task(text: &str, index: usize) -> Option<String> {
+    let (range, checked) = Parser::new_ext(text, options())
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::TaskListMarker(checked) => Some((range, checked)),
+            _ => None,
+        })
+        .nth(index)?;
+    let mark = range.start + text[range].find('[')? + 1;
+    let mut toggled = text.to_string();
+    toggled.replace_range(mark..mark + 1, if checked { " " } else { "x" });
+    Some(toggled)
+}
+
+/// Escapes `text` for HTML element content and quoted attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Keeps only the formatting elements Markdown produces and links with
+/// harmless schemes; scripts, styles, frames, event handlers and
+/// `javascript:` URLs are removed.
+pub fn sanitize(html: &str) -> String {
+    ammonia::Builder::default()
+        .add_tag_attributes("a", &["class", "data-key"])
+        .add_tag_attributes("code", &["class"])
+        .add_tag_attributes("span", &["class", "role", "aria-checked", "data-task"])
+        .add_tag_attributes("sup", &["class"])
+        .add_tag_attributes("div", &["class", "id"])
+        .add_tags(MATHML_TAGS)
+        .add_tag_attributes("math", &["display"])
+        .add_tag_attributes("mi", &["mathvariant"])
+        .add_tag_attributes("mo", &["stretchy", "form", "accent"])
+        .add_tag_attributes("mover", &["accent"])
+        .add_tag_attributes("munder", &["accentunder"])
+        .add_tag_attributes("mfrac", &["linethickness"])
+        .add_tag_attributes("mspace", &["width"])
+        .add_tag_attributes("mstyle", &["displaystyle"])
+        .add_tag_attributes("mtable", &["columnalign"])
+        .add_tag_attributes("th", &["style"])
+        .add_tag_attributes("td", &["style"])
+        .id_prefix(Some(ID_PREFIX))
+        .attribute_filter(|element, attribute, value| {
+            let allowed = match (element, attribute) {
+                // Only pulldown-cmark's fenced-code language marker
+                ("code", "class") => value.starts_with("language-") || value == "math-error",
+                ("a", "class") => value == "wiki-link",
+                ("a", "data-key") => NoteKey::parse(value).is_some(),
+                ("span", "class") => {
+                    matches!(value, "task-box" | "wiki-link")
+                        || value.split(' ').all(|class| class.strip_prefix(HIGHLIGHT_PREFIX).is_some_and(|scope| scope.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')))
+                }
+                ("span", "role") => value == "checkbox",
+                ("span", "aria-checked") => matches!(value, "true" | "false"),
+                ("span", "data-task") => value.bytes().all(|b| b.is_ascii_digit()),
+                ("sup", "class") => matches!(value, "footnote-reference" | "footnote-definition-label"),
+                ("div", "class") => value == "footnote-definition",
+                // Column alignment of GFM tables
+                ("th" | "td", "style") => matches!(value, "text-align: left" | "text-align: center" | "text-align: right"),
+                // In-note anchors follow their prefixed ids
+                ("a", "href") if value.starts_with('#') => return Some(Cow::Owned(format!("#{ID_PREFIX}{}", &value[1..]))),
+                _ => true,
+            };
+            allowed.then_some(Cow::Borrowed(value))
+        })
+        .clean(html)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_script_elements() {
+        let html = render_markdown("Before\n\n<script>window.ipc.postMessage('pwned')</script>\n\nAfter");
+        assert!(!html.contains("<script"), "{html}");
+        assert!(!html.contains("postMessage"), "{html}");
+        assert!(html.contains("Before") && html.contains("After"));
+    }
+
+    #[test]
+    fn strips_event_handlers() {
+        let html = render_markdown("<img src=\"x\" onerror=\"alert(1)\"> <b onmouseover=\"alert(2)\">bold</b>");
+        assert!(!html.contains("onerror") && !html.contains("onmouseover"), "{html}");
+        assert!(!html.contains("alert"), "{html}");
+        assert!(html.contains("<b>bold</b>"), "{html}");
+    }
+
+    #[test]
+    fn strips_javascript_urls() {
+        for note in [
+            "[click](javascript:alert(1))",
+            "<a href=\"JaVaScRiPt:alert(1)\">click</a>",
+            "<a href=\"  javascript:alert(1)\">click</a>",
+            "<img src=\"javascript:alert(1)\">",
+        ] {
+            let html = render_markdown(note);
+            assert!(!html.to_lowercase().contains("javascript:"), "{note} -> {html}");
+        }
+    }
+
+    #[test]
+    fn strips_frames_styles_and_forms() {
+        let html = render_markdown("<iframe src=\"https://example.com\"></iframe><style>body{display:none}</style><form><input></form>");
+        for tag in ["<iframe", "<style", "<form", "<input", "display:none"] {
+            assert!(!html.contains(tag), "{tag} in {html}");
+        }
+    }
+
+    #[test]
+    fn keeps_markdown_formatting() {
+        let html = render_markdown("# Dawn\n\n*Quiet* and **gold**, see [the river](https://example.com).\n\n```rust\nfn main() {}\n```\n\n> A heron.\n\n- one\n- two");
+        for fragment in ["<h1>Dawn</h1>", "<em>Quiet</em>", "<strong>gold</strong>", "href=\"https://example.com\"", "<code class=\"language-rust\">", "<blockquote>", "<li>one</li>"] {
+            assert!(html.contains(fragment), "{fragment} missing from {html}");
+        }
+    }
+
+    #[test]
+    fn escapes_text_that_looks_like_markup() {
+        let html = render_markdown("`<script>alert(1)</script>`");
+        assert!(html.contains("&lt;script&gt;"), "{html}");
+    }
+
+    #[test]
+    fn renders_gfm_extensions() {
+        let html = render_markdown("| Hour | Mood |\n|:-----|:----:|\n| 9 | calm |\n\n~~rushed~~\n\nA note[^tea].\n\n[^tea]: Green, no sugar.");
+        for fragment in ["<table>", "<th style=\"text-align: left\">Hour</th>", "<td style=\"text-align: center\">calm</td>", "<del>rushed</del>"] {
+            assert!(html.contains(fragment), "{fragment} missing from {html}");
+        }
+        assert!(html.contains("<sup class=\"footnote-reference\"><a href=\"#note-tea\""), "{html}");
+        assert!(html.contains("<div class=\"footnote-definition\" id=\"note-tea\">"), "{html}");
+    }
+
+    #[test]
+    fn numbers_task_boxes_in_order() {
+        let html = render_markdown("- [ ] water the fig\n- [x] read\n\n```\n- [ ] not a task\n```\n\n* [X] walk");
+        assert!(html.contains("aria-checked=\"false\" data-task=\"0\""), "{html}");
+        assert!(html.contains("aria-checked=\"true\" data-task=\"1\""), "{html}");
+        assert!(html.contains("aria-checked=\"true\" data-task=\"2\""), "{html}");
+        assert!(!html.contains("data-task=\"3\""), "{html}");
+    }
+
+    #[test]
+    fn rejects_forged_attribute_values() {
+        let html = render_markdown("<span class=\"task-box evil\" data-task=\"1;x\" role=\"button\">x</span><th style=\"background:url(x)\">");
+        for fragment in ["evil", "1;x", "button", "background"] {
+            assert!(!html.contains(fragment), "{fragment} in {html}");
+        }
+    }
+
+    #[test]
+    fn toggles_only_the_chosen_task() {
+        let note = "Plan\n\n```\n- [ ] not a task\n```\n\n- [ ] water the fig\n  - [X] read\n1. [ ] walk";
+        assert_eq!(toggle_task(note, 0).unwrap(), note.replacen("- [ ] water", "- [x] water", 1));
+        assert_eq!(toggle_task(note, 1).unwrap(), note.replace("[X] read", "[ ] read"));
+        assert_eq!(toggle_task(note, 2).unwrap(), note.replace("[ ] walk", "[x] walk"));
+        assert_eq!(toggle_task(note, 3), None);
+        assert_eq!(toggle_task(&toggle_task(note, 0).unwrap(), 0).unwrap(), note);
+    }
+
+    #[test]
+    fn links_hours_named_in_wiki_links() {
+        let href = |key: NoteKey| (key.hour != 3).then(|| format!("#{key}"));
+        let links = WikiLinks { date: NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(), href: &href };
+        let html = render_note("See [[2026-10-01 14]], [[yesterday 9]] and [[tomorrow 3]], not [[someday]] or `[[today 9]]`.", &links);
+        assert!(html.contains("<a class=\"wiki-link\" href=\"#note-2026-10-01-14\" data-key=\"2026-10-01-14\""), "{html}");
+        assert!(html.contains("data-key=\"2026-10-16-09\" rel=\"noopener noreferrer\">yesterday 9</a>"), "{html}");
+        assert!(html.contains("<span class=\"wiki-link\">tomorrow 3</span>"), "{html}");
+        assert!(html.contains("[[someday]]") && html.contains("<code>[[today 9]]</code>"), "{html}");
+        assert!(render_markdown("[[2026-10-01 14]]").contains("[[2026-10-01 14]]"));
+    }
+
+    #[test]
+    fn highlights_fenced_code_by_language() {
+        let html = render_markdown("```rust\nfn main() { let s = \"<b>\"; } // done\n```\n\n```klingon\nqapla'\n```");
+        assert!(html.contains("<pre><code class=\"language-rust\"><span class=\"hl-source hl-rust\">"), "{html}");
+        for scope in ["hl-storage", "hl-string", "hl-comment"] {
+            assert!(html.contains(scope), "{scope} missing from {html}");
+        }
+        assert!(html.contains("&lt;b&gt;") && !html.contains("<b>"), "{html}");
+        assert!(html.contains("<pre><code class=\"language-klingon\">qapla'\n</code></pre>"), "{html}");
+    }
+
+    #[test]
+    fn renders_math_to_mathml() {
+        let html = render_markdown("Euler: $e^{i\\pi} + 1 = 0$, costing $5 or $10.\n\n$$\\frac{a}{\\sqrt{b}} < \\sum_{n=0}^\\infty x_n$$\n\n`$not math$`");
+        assert!(html.contains("<math display=\"inline\"><msup><mi>e</mi><mrow><mi>i</mi><mi>π</mi></mrow></msup>"), "{html}");
+        assert!(html.contains("costing $5 or $10."), "{html}");
+        assert!(html.contains("<math display=\"block\"><mfrac><mi>a</mi><msqrt><mi>b</mi></msqrt></mfrac><mo>&lt;</mo>"), "{html}");
+        assert!(html.contains("<code>$not math$</code>"), "{html}");
+    }
+
+    #[test]
+    fn shows_unreadable_math_as_written() {
+        let html = render_markdown("$\\left( x$ and $x & y$");
+        assert_eq!(html.matches("class=\"math-error\"").count(), 2, "{html}");
+        assert!(html.contains("\\left( x</code>") && html.contains("x &amp; y</code>"), "{html}");
+        assert!(!html.contains("PARSE ERROR"), "{html}");
+    }
+
+    #[test]
+    fn keeps_math_free_of_markup() {
+        let html = render_markdown("$\\text{<img src=x onerror=alert(1)>}$ $$\\text{<script>alert(2)</script>}$$");
+        assert!(!html.contains("<script") && !html.contains("<img"), "{html}");
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Bound;
+
+// --- Data Structures ---
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimeNote {
+    pub content: String,
+    pub is_locked: bool, // "Banked" experience vs planned
+}
+
+/// Where an hour stands in the lifecycle: nothing written, planned ahead
+/// (still editable), or banked (elapsed and sealed into the ledger).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HourState {
+    Empty,
+    Planned,
+    Banked,
+}
+
+impl HourState {
+    pub fn of(note: Option<&TimeNote>) -> Self {
+        match note {
+            None => HourState::Empty,
+            Some(note) if note.is_locked => HourState::Banked,
+            Some(_) => HourState::Planned,
+        }
+    }
+}
+
+/// Heatmap shade (0-4) for a day with `hours` of its 24 hours written.
+pub fn coverage_level(hours: usize) -> usize {
+    match hours {
+        0 => 0,
+        1..=3 => 1,
+        4..=8 => 2,
+        9..=15 => 3,
+        _ => 4,
+    }
+}
+
+/// Full Date-Hour Key (YYYY-MM-DD-HH) addressing one of the 24 hours of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NoteKey {
+    pub date: NaiveDate,
+    pub hour: u32, // 0-23
+}
+
+/// The archive, ordered chronologically by key.
+pub type Notes = BTreeMap<NoteKey, TimeNote>;
+
+impl NoteKey {
+    pub fn new(date: NaiveDate, hour: u32) -> Option<Self> {
+        (hour < 24).then_some(Self { date, hour })
+    }
+
+    /// Local wall-clock moment the hour begins.
+    pub fn start(&self) -> NaiveDateTime {
+        self.date.and_hms_opt(self.hour, 0, 0).expect("hour is always below 24")
+    }
+
+    /// Whether the whole hour lies before `now`, making it due for banking.
+    pub fn has_elapsed(&self, now: NaiveDateTime) -> bool {
+        self.start() + TimeDelta::hours(1) <= now
+    }
+
+    /// Whether the hour has begun; only begun hours can be banked by hand.
+    pub fn has_begun(&self, now: NaiveDateTime) -> bool {
+        self.start() <= now
+    }
+
+    /// Parses the canonical zero-padded `YYYY-MM-DD-HH` form.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (date, hour) = s.rsplit_once('-')?;
+        if date.len() != 10 || hour.len() != 2 || !hour.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+        Self::new(date, hour.parse().ok()?)
+    }
+
+    /// Bounds of every key dated within `from..=to`, either end left open by
+    /// `None`; `None` if the range is empty.
+    pub fn span(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Option<(Bound<Self>, Bound<Self>)> {
+        if from.zip(to).is_some_and(|(from, to)| from > to) {
+            return None;
+        }
+        let start = from.map_or(Bound::Unbounded, |date| Bound::Included(Self { date, hour: 0 }));
+        let end = to.map_or(Bound::Unbounded, |date| Bound::Included(Self { date, hour: 23 }));
+        Some((start, end))
+    }
+
+    /// Parses the unpadded `YYYY-MM-DD-H` keys written by the old 12-marker dial.
+    /// Those carried no AM/PM information, so marker `h` maps to hour `h`.
+    pub fn parse_legacy(s: &str) -> Option<Self> {
+        let (date, hour) = s.rsplit_once('-')?;
+        if hour.is_empty() || hour.len() > 2 || !hour.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let hour: u32 = hour.parse().ok()?;
+        if hour >= 12 {
+            return None;
+        }
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+        Self::new(date, hour)
+    }
+}
+
+impl fmt::Display for NoteKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{:02}", self.date.format("%Y-%m-%d"), self.hour)
+    }
+}
+
+impl Serialize for NoteKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for NoteKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        NoteKey::parse(&s).ok_or_else(|| serde::de::Error::custom(format!("invalid date-hour key `{s}`")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_canonical_keys() {
+        assert_eq!(NoteKey::parse("2026-10-17-09"), NoteKey::new(date(2026, 10, 17), 9));
+        assert_eq!(NoteKey::parse("2026-10-17-23"), NoteKey::new(date(2026, 10, 17), 23));
+        assert_eq!(NoteKey::parse("2026-10-17-00").unwrap().to_string(), "2026-10-17-00");
+        for bad in ["2026-10-17-9", "2026-10-17-24", "2026-10-17-009", "2026-10-17-+9", "2026-1-17-09", "2026-02-30-09", "2026-10-17", ""] {
+            assert_eq!(NoteKey::parse(bad), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn parses_legacy_twelve_marker_keys() {
+        assert_eq!(NoteKey::parse_legacy("2026-10-17-9"), NoteKey::new(date(2026, 10, 17), 9));
+        assert_eq!(NoteKey::parse_legacy("2026-10-17-0"), NoteKey::new(date(2026, 10, 17), 0));
+        assert_eq!(NoteKey::parse_legacy("2026-10-17-09"), NoteKey::new(date(2026, 10, 17), 9));
+        for bad in ["2026-10-17-12", "2026-10-17-23", "2026-10-17-", "2026-10-17-123", "2026-10-17-x", "2026-13-01-1"] {
+            assert_eq!(NoteKey::parse_legacy(bad), None, "{bad}");
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use crate::note::{NoteKey, TimeNote};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+// --- Full-Text Search ---
+
+/// Words of context kept on either side of the first match in a snippet.
+const SNIPPET_CONTEXT: usize = 8;
+
+/// Word positions `start..end` a query matched within one note.
+type Spans = Vec<Range<usize>>;
+
+/// One indexed note: its text and the byte range of every word in it.
+#[derive(Clone, Debug)]
+struct Document {
+    content: String,
+    words: Vec<Range<usize>>,
+}
+
+/// Inverted index over every note's content, mapping each lowercased word
+/// to the notes and word positions it occurs at.
+#[derive(Clone, Debug, Default)]
+pub struct SearchIndex {
+    docs: BTreeMap<NoteKey, Document>,
+    // Sorted by word so prefix queries are a range scan
+    postings: BTreeMap<String, BTreeMap<NoteKey, Vec<usize>>>,
+}
+
+/// A parsed query: every clause must match, within the optional date range.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Query {
+    pub clauses: Vec<Clause>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Clause {
+    /// A whole word.
+    Term(String),
+    /// Any word starting with this, written `word*`.
+    Prefix(String),
+    /// Consecutive words, written `"like this"`.
+    Phrase(Vec<String>),
+}
+
+/// One matching note, best first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hit {
+    pub key: NoteKey,
+    pub score: f64,
+    /// Text around the first match, split into (text, is_match) runs.
+    pub snippet: Vec<(String, bool)>,
+}
+
+impl SearchIndex {
+    pub fn build<'a>(notes: impl IntoIterator<Item = (&'a NoteKey, &'a TimeNote)>) -> Self {
+        let mut index = SearchIndex::default();
+        for (key, note) in notes {
+            index.update(*key, Some(note));
+        }
+        index
+    }
+
+    /// Re-indexes `key` after an edit; `None` drops it from the index.
+    pub fn update(&mut self, key: NoteKey, note: Option<&TimeNote>) {
+        if let Some(old) = self.docs.remove(&key) {
+            for word in &old.words {
+                let term = old.content[word.clone()].to_lowercase();
+                if let Some(notes) = self.postings.get_mut(&term) {
+                    notes.remove(&key);
+                    if notes.is_empty() {
+                        self.postings.remove(&term);
+                    }
+                }
+            }
+        }
+        let Some(note) = note else { return };
+        let words = tokenize(&note.content);
+        for (pos, (_, term)) in words.iter().enumerate() {
+            self.postings.entry(term.clone()).or_default().entry(key).or_default().push(pos);
+        }
+        let words = words.into_iter().map(|(range, _)| range).collect();
+        self.docs.insert(key, Document { content: note.content.clone(), words });
+    }
+
+    /// Notes matching every clause of `query`, ranked by how often and how
+    /// distinctively they match, most recent first among equals. A query
+    /// with only a date range lists every note in it.
+    pub fn search(&self, query: &Query) -> Vec<Hit> {
+        let in_range = |key: &NoteKey| query.from.is_none_or(|d| key.date >= d) && query.to.is_none_or(|d| key.date <= d);
+        if query.clauses.is_empty() && query.from.is_none() && query.to.is_none() {
+            return Vec::new();
+        }
+
+        // Matched word spans per note, and the running score
+        let mut matched: Option<BTreeMap<NoteKey, (f64, Spans)>> = None;
+        if query.clauses.is_empty() {
+            matched = Some(self.docs.keys().map(|key| (*key, (0.0, Vec::new()))).collect());
+        }
+        for clause in &query.clauses {
+            let spans = self.matches(clause);
+            // Rarer clauses weigh more
+            let idf = (1.0 + self.docs.len() as f64 / spans.len().max(1) as f64).ln();
+            let score = |spans: &[Range<usize>]| idf * (1.0 + (spans.len() as f64).ln());
+            matched = Some(match matched {
+                None => spans.into_iter().map(|(key, spans)| (key, (score(&spans), spans))).collect(),
+                Some(so_far) => so_far
+                    .into_iter()
+                    .filter_map(|(key, (total, mut all))| {
+                        let spans = spans.get(&key)?;
+                        all.extend(spans.iter().cloned());
+                        Some((key, (total + score(spans), all)))
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut hits: Vec<Hit> = matched
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| in_range(key))
+            .map(|(key, (score, spans))| Hit { key, score, snippet: self.snippet(&self.docs[&key], spans) })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.key.cmp(&a.key)));
+        hits
+    }
+
+    /// Every note `clause` matches, with the word spans it matched.
+    fn matches(&self, clause: &Clause) -> BTreeMap<NoteKey, Spans> {
+        let mut found: BTreeMap<NoteKey, Spans> = BTreeMap::new();
+        match clause {
+            Clause::Term(term) => {
+                for (key, positions) in self.postings.get(term).into_iter().flatten() {
+                    found.insert(*key, positions.iter().map(|&p| p..p + 1).collect());
+                }
+            }
+            Clause::Prefix(prefix) => {
+                let terms = self.postings.range(prefix.clone()..).take_while(|(term, _)| term.starts_with(prefix.as_str()));
+                for (_, notes) in terms {
+                    for (key, positions) in notes {
+                        found.entry(*key).or_default().extend(positions.iter().map(|&p| p..p + 1));
+                    }
+                }
+                for spans in found.values_mut() {
+                    spans.sort_by_key(|s| s.start);
+                }
+            }
+            Clause::Phrase(terms) => {
+                let Some((first, rest)) = terms.split_first() else { return found };
+                for (key, positions) in self.postings.get(first).into_iter().flatten() {
+                    let follows = |p: usize| {
+                        rest.iter().enumerate().all(|(i, term)| {
+                            self.postings.get(term).and_then(|notes| notes.get(key)).is_some_and(|at| at.binary_search(&(p + i + 1)).is_ok())
+                        })
+                    };
+                    let spans: Vec<_> = positions.iter().filter(|&&p| follows(p)).map(|&p| p..p + terms.len()).collect();
+                    if !spans.is_empty() {
+                        found.insert(*key, spans);
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// A few words either side of the earliest match, with every match inside marked.
+    fn snippet(&self, doc: &Document, mut spans: Spans) -> Vec<(String, bool)> {
+        if doc.words.is_empty() {
+            return vec![(collapse(&doc.content), false)];
+        }
+        spans.sort_by_key(|s| s.start);
+        let first = spans.first().cloned().unwrap_or(0..1);
+        let start = first.start.saturating_sub(SNIPPET_CONTEXT);
+        let end = (first.end + SNIPPET_CONTEXT).min(doc.words.len());
+
+        let mut snippet = Vec::new();
+        let mut push = |text: &str, is_match: bool| {
+            let text = collapse(text);
+            if !text.is_empty() {
+                snippet.push((text, is_match));
+            }
+        };
+        let mut cursor = doc.words[start].start;
+        if start > 0 {
+            push("… ", false);
+        }
+        for span in spans.iter().filter(|s| s.start >= start && s.end <= end) {
+            let from = doc.words[span.start].start;
+            if from < cursor {
+                continue;
+            }
+            push(&doc.content[cursor..from], false);
+            cursor = doc.words[span.end - 1].end;
+            push(&doc.content[from..cursor], true);
+        }
+        push(&doc.content[cursor..doc.words[end - 1].end], false);
+        if end < doc.words.len() {
+            push(" …", false);
+        }
+        snippet
+    }
+}
+
+impl Query {
+    /// Parses words, `"quoted phrases"`, `prefix*` and `from:`/`to:` dates
+    /// (`YYYY-MM-DD`). Words are matched case-insensitively.
+    pub fn parse(text: &str) -> Self {
+        let mut query = Query::default();
+        let mut rest = text.trim_start();
+        while !rest.is_empty() {
+            let (token, quoted, tail) = match rest.strip_prefix('"') {
+                Some(inner) => {
+                    let end = inner.find('"').unwrap_or(inner.len());
+                    (&inner[..end], true, inner.get(end + 1..).unwrap_or(""))
+                }
+                None => {
+                    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                    (&rest[..end], false, &rest[end..])
+                }
+            };
+            rest = tail.trim_start();
+
+            if !quoted {
+                let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok();
+                if let Some(from) = token.strip_prefix("from:").and_then(date) {
+                    query.from = Some(from);
+                    continue;
+                }
+                if let Some(to) = token.strip_prefix("to:").and_then(date) {
+                    query.to = Some(to);
+                    continue;
+                }
+            }
+            let prefix = !quoted && token.ends_with('*');
+            let mut terms: Vec<String> = tokenize(token).into_iter().map(|(_, term)| term).collect();
+            match terms.len() {
+                0 => {}
+                1 if prefix => query.clauses.push(Clause::Prefix(terms.remove(0))),
+                1 => query.clauses.push(Clause::Term(terms.remove(0))),
+                _ => query.clauses.push(Clause::Phrase(terms)),
+            }
+        }
+        query
+    }
+}
+
+/// Splits `text` into lowercased alphanumeric words with their byte ranges.
+fn tokenize(text: &str) -> Vec<(Range<usize>, String)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (start, c.is_alphanumeric()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                words.push((s..i, text[s..i].to_lowercase()));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Folds line breaks and runs of whitespace into single spaces.
+fn collapse(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space {
+            out.push(' ');
+            space = false;
+        }
+        out.push(c);
+    }
+    if space {
+        out.push(' ');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> NoteKey {
+        NoteKey::parse(s).unwrap()
+    }
+
+    fn index(notes: &[(&str, &str)]) -> SearchIndex {
+        let notes: crate::note::Notes =
+            notes.iter().map(|(k, content)| (key(k), TimeNote { content: content.to_string(), is_locked: false })).collect();
+        SearchIndex::build(&notes)
+    }
+
+    fn keys(hits: &[Hit]) -> Vec<NoteKey> {
+        hits.iter().map(|hit| hit.key).collect()
+    }
+
+    #[test]
+    fn tokenizes_unicode_words_with_byte_ranges() {
+        let text = "Café—naïve ZEIT, 42x!";
+        let words = tokenize(text);
+        let terms: Vec<&str> = words.iter().map(|(_, term)| term.as_str()).collect();
+        assert_eq!(terms, ["café", "naïve", "zeit", "42x"]);
+        let spelled: Vec<&str> = words.iter().map(|(range, _)| &text[range.clone()]).collect();
+        assert_eq!(spelled, ["Café", "naïve", "ZEIT", "42x"]);
+    }
+
+    #[test]
+    fn parses_phrases_prefixes_and_dates() {
+        let query = Query::parse(r#"  Dawn "Gold  light" sun* "alone" from:2026-10-01 to:2026-10-31 from:soon "open end"#);
+        assert_eq!(
+            query.clauses,
+            [
+                Clause::Term("dawn".into()),
+                Clause::Phrase(vec!["gold".into(), "light".into()]),
+                Clause::Prefix("sun".into()),
+                Clause::Term("alone".into()),
+                Clause::Phrase(vec!["from".into(), "soon".into()]),
+                Clause::Phrase(vec!["open".into(), "end".into()]),
+            ]
+        );
+        assert_eq!(query.from, NaiveDate::from_ymd_opt(2026, 10, 1));
+        assert_eq!(query.to, NaiveDate::from_ymd_opt(2026, 10, 31));
+        assert_eq!(Query::parse(r#"* "" -"#), Query::default());
+    }
+
+    #[test]
+    fn matches_every_clause_within_the_range() {
+        let index = index(&[
+            ("2026-10-16-07", "Gold light at dawn over the sundial"),
+            ("2026-10-17-07", "Light gold, then sunrise"),
+            ("2026-10-17-20", "Sunset in gold light"),
+        ]);
+        assert_eq!(keys(&index.search(&Query::parse(r#""gold light""#))), [key("2026-10-17-20"), key("2026-10-16-07")]);
+        assert_eq!(keys(&index.search(&Query::parse("gold sun*"))).len(), 3);
+        assert_eq!(keys(&index.search(&Query::parse("sunr*"))), [key("2026-10-17-07")]);
+        assert_eq!(keys(&index.search(&Query::parse("sun"))), []);
+        assert_eq!(keys(&index.search(&Query::parse("gold dawn"))), [key("2026-10-16-07")]);
+        assert_eq!(keys(&index.search(&Query::parse("light from:2026-10-17"))), [key("2026-10-17-20"), key("2026-10-17-07")]);
+        assert_eq!(keys(&index.search(&Query::parse("to:2026-10-16"))), [key("2026-10-16-07")]);
+        assert!(index.search(&Query::default()).is_empty());
+    }
+
+    #[test]
+    fn drops_a_note_once_edited_away() {
+        let mut index = index(&[("2026-10-17-09", "Ephemeral thought")]);
+        index.update(key("2026-10-17-09"), Some(&TimeNote { content: "Lasting thought".into(), is_locked: false }));
+        assert!(index.search(&Query::parse("ephemeral")).is_empty());
+        index.update(key("2026-10-17-09"), None);
+        assert!(index.search(&Query::parse("thought")).is_empty());
+    }
+
+    #[test]
+    fn snippets_cut_at_word_boundaries_in_multibyte_text() {
+        let content = "Ἐν ἀρχῇ ἦν ὁ λόγος, καὶ ὁ λόγος ἦν πρὸς τὸν θεόν — 時間 は 流れる 川 の よう だ と 彼 は 言った 、 金色 の 時間 。";
+        let hits = index(&[("2026-10-17-09", content)]).search(&Query::parse("流れる"));
+        let hit = &hits[0];
+        let text: String = hit.snippet.iter().map(|(text, _)| text.as_str()).collect();
+        assert_eq!(text, "… ὁ λόγος ἦν πρὸς τὸν θεόν — 時間 は 流れる 川 の よう だ と 彼 は 言った …");
+        let marked: Vec<&str> = hit.snippet.iter().filter(|(_, is_match)| *is_match).map(|(text, _)| text.as_str()).collect();
+        assert_eq!(marked, ["流れる"]);
+
+        // A short note is shown whole, line breaks folded
+        let hits = index(&[("2026-10-17-10", "Über\n\nalles  Größe")]).search(&Query::parse("größe"));
+        assert_eq!(hits[0].snippet, [("Über alles ".to_string(), false), ("Größe".to_string(), true)]);
+    }
+}
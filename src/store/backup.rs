@@ -0,0 +1,176 @@
+use chrono::Local;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Directory, beside the archive, holding its rotating snapshots.
+const BACKUP_DIR: &str = "chronos_backups";
+
+/// Replaces `path` with `data` without ever exposing a half-written file:
+/// the bytes go to a sibling temp file, are fsynced, then renamed over the original.
+pub fn write_atomic(path: &Path, data: &[u8]) -> io::Result<()> {
+    let file_name = path.file_name().ok_or_else(|| io::Error::other("archive path has no file name"))?;
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+
+    let mut file = File::create(&tmp)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&tmp, path)?;
+
+    // Persist the rename itself; directories cannot be opened for syncing on Windows
+    #[cfg(unix)]
+    if let Some(dir) = parent_dir(path) {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+/// Copies the current archive into a timestamped snapshot and prunes all
+/// but the newest `keep` snapshots. Does nothing if `keep` is 0 or the
+/// archive does not exist yet.
+pub fn rotate(path: &Path, keep: usize) -> io::Result<()> {
+    if keep == 0 || !path.exists() {
+        return Ok(());
+    }
+    let (stem, ext) = name_parts(path);
+    let dir = backup_dir(path);
+    fs::create_dir_all(&dir)?;
+
+    let stamp = Local::now().format("%Y%m%dT%H%M%S%3f");
+    fs::copy(path, dir.join(format!("{stem}-{stamp}{ext}")))?;
+
+    let mut snapshots = list(path)?;
+    // Timestamps sort lexically, so the oldest come first
+    snapshots.sort();
+    let excess = snapshots.len().saturating_sub(keep);
+    for old in &snapshots[..excess] {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+/// Copies a damaged archive aside as `<name>.corrupt-<timestamp>.<ext>`
+/// so it survives whatever is written in its place.
+pub fn quarantine(path: &Path) -> io::Result<PathBuf> {
+    let (stem, ext) = name_parts(path);
+    let stamp = Local::now().format("%Y%m%dT%H%M%S%3f");
+    let target = path.with_file_name(format!("{stem}.corrupt-{stamp}{ext}"));
+    fs::copy(path, &target)?;
+    Ok(target)
+}
+
+/// Every snapshot of `path`, unordered.
+pub fn list(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let (stem, ext) = name_parts(path);
+    let prefix = format!("{stem}-");
+    let entries = match fs::read_dir(backup_dir(path)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut snapshots = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with(&prefix) && name.ends_with(&ext) {
+            snapshots.push(entry.path());
+        }
+    }
+    Ok(snapshots)
+}
+
+fn parent_dir(path: &Path) -> Option<&Path> {
+    path.parent().filter(|p| !p.as_os_str().is_empty())
+}
+
+fn backup_dir(path: &Path) -> PathBuf {
+    parent_dir(path).unwrap_or(Path::new(".")).join(BACKUP_DIR)
+}
+
+fn name_parts(path: &Path) -> (String, String) {
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let ext = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (stem, ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn atomic_write_replaces_the_file_and_leaves_no_temp_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        write_atomic(&path, b"first").unwrap();
+        write_atomic(&path, b"second").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"second");
+        let names: Vec<_> = fs::read_dir(dir.path()).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(names, ["notes.json"]);
+    }
+
+    #[test]
+    fn rotation_keeps_the_newest_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        rotate(&path, 3).unwrap();
+        assert!(list(&path).unwrap().is_empty(), "nothing to snapshot before the archive exists");
+
+        for generation in 0..5 {
+            fs::write(&path, format!("generation {generation}")).unwrap();
+            rotate(&path, 3).unwrap();
+            // Snapshot names carry millisecond timestamps
+            thread::sleep(Duration::from_millis(5));
+        }
+        let mut snapshots = list(&path).unwrap();
+        snapshots.sort();
+        let kept: Vec<_> = snapshots.iter().map(|p| fs::read_to_string(p).unwrap()).collect();
+        assert_eq!(kept, ["generation 2", "generation 3", "generation 4"]);
+
+        for snapshot in &snapshots {
+            let name = snapshot.file_name().unwrap().to_string_lossy();
+            let stamp = name.strip_prefix("notes-").and_then(|rest| rest.strip_suffix(".json")).unwrap();
+            assert!(chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%dT%H%M%S%3f").is_ok(), "{name}");
+            assert_eq!(snapshot.parent().unwrap(), dir.path().join(BACKUP_DIR));
+        }
+    }
+
+    #[test]
+    fn rotation_is_off_when_keeping_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        fs::write(&path, "{}").unwrap();
+        rotate(&path, 0).unwrap();
+        assert!(!dir.path().join(BACKUP_DIR).exists());
+    }
+
+    #[test]
+    fn listing_ignores_other_archives() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        fs::write(&path, "{}").unwrap();
+        rotate(&path, 2).unwrap();
+        let other = dir.path().join("other.json");
+        fs::write(&other, "{}").unwrap();
+        rotate(&other, 2).unwrap();
+        fs::write(dir.path().join(BACKUP_DIR).join("notes-stray.txt"), "").unwrap();
+        assert_eq!(list(&path).unwrap().len(), 1);
+        assert_eq!(list(&other).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn quarantine_copies_the_damaged_file_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        fs::write(&path, "{ broken").unwrap();
+        let target = quarantine(&path).unwrap();
+        assert!(target.file_name().unwrap().to_string_lossy().starts_with("notes.corrupt-"));
+        assert_eq!(fs::read_to_string(&target).unwrap(), "{ broken");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ broken");
+    }
+}
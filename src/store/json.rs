@@ -0,0 +1,468 @@
+use super::{backup, MemoryStore, NoteStore, StoreError};
+use crate::integrity::{self, IntegrityReport};
+use crate::note::{NoteKey, Notes, TimeNote};
+use chrono::NaiveDate;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The original `chronos_notes.json` archive: the whole map, held in memory
+/// and atomically rewritten when flushed.
+pub struct JsonStore {
+    path: PathBuf,
+    notes: MemoryStore,
+    // Changes not yet written to disk
+    dirty: bool,
+    // SHA-256 of the bytes most recently written, to detect later tampering
+    written_digest: Option<String>,
+    // SHA-256 of the archive as this store last read or wrote it, to notice
+    // another process (such as the command line) writing in the meantime
+    disk_digest: Option<String>,
+    // Keys put or deleted since the archive was last written
+    touched: HashSet<NoteKey>,
+    backups: usize,
+    // Whether this session has already snapshotted the archive it opened
+    backed_up: bool,
+}
+
+impl JsonStore {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let path = path.into();
+        let mut migrated = false;
+        // Only a missing file is an empty vault; any other read failure must not be written over
+        let data = match fs::read(&path) {
+            Ok(bytes) => Some(String::from_utf8(bytes).map_err(|e| {
+                Recovery::attempt(&path, &String::from_utf8_lossy(e.as_bytes()), e.utf8_error())
+            })?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let notes = if let Some(data) = &data {
+            let raw: HashMap<String, TimeNote> = serde_json::from_str(data).map_err(|e| Recovery::attempt(&path, data, e))?;
+            // Keep an untouched copy of an archive written before the 24-hour key migration
+            let legacy_backup = legacy_backup_path(&path);
+            if raw.keys().any(|k| NoteKey::parse(k).is_none()) && !fs::exists(&legacy_backup)? {
+                fs::write(&legacy_backup, data)?;
+            }
+            migrated = raw.keys().any(|k| NoteKey::parse(k).is_none() && NoteKey::parse_legacy(k).is_some());
+            migrate_notes(raw)
+        } else {
+            Notes::new()
+        };
+        let mut store = Self {
+            path,
+            notes: MemoryStore::new(notes),
+            dirty: false,
+            written_digest: None,
+            disk_digest: data.as_deref().map(|data| integrity::digest(data.as_bytes())),
+            touched: HashSet::new(),
+            backups: 0,
+            backed_up: false,
+        };
+        // The migration is one-time: rewrite the legacy keys on disk straight away
+        if migrated {
+            store.write()?;
+        }
+        Ok(store)
+    }
+
+    /// Keeps up to `keep` rotating snapshots of the archive in `chronos_backups/`.
+    pub fn with_backups(mut self, keep: usize) -> Self {
+        self.backups = keep;
+        self
+    }
+
+    fn save(&mut self) -> Result<(), StoreError> {
+        // Snapshot what was on disk before this session's first overwrite
+        if !self.backed_up {
+            backup::rotate(&self.path, self.backups)?;
+            self.backed_up = true;
+        }
+        self.catch_up()?;
+        self.write()
+    }
+
+    /// Takes in whatever another process wrote to the archive since this
+    /// store last read or wrote it, so saving does not undo it. Where both
+    /// changed the same hour, this store's version wins.
+    fn catch_up(&mut self) -> Result<(), StoreError> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        if self.disk_digest.as_deref() == Some(integrity::digest(&bytes).as_str()) {
+            return Ok(());
+        }
+        let Ok(raw) = serde_json::from_slice::<HashMap<String, TimeNote>>(&bytes) else {
+            // Nothing to take in, but keep a copy of what is about to be replaced
+            backup::quarantine(&self.path)?;
+            return Ok(());
+        };
+        let mut merged = migrate_notes(raw);
+        for key in &self.touched {
+            match self.notes.get(*key)? {
+                Some(note) => merged.insert(*key, note),
+                None => merged.remove(key),
+            };
+        }
+        self.notes = MemoryStore::new(merged);
+        Ok(())
+    }
+
+    /// Rewrites the archive from the open vault.
+    fn write(&mut self) -> Result<(), StoreError> {
+        let data = serde_json::to_string_pretty(self.notes.notes())?;
+        backup::write_atomic(&self.path, data.as_bytes())?;
+        let digest = integrity::digest(data.as_bytes());
+        self.written_digest = Some(digest.clone());
+        self.disk_digest = Some(digest);
+        self.touched.clear();
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+impl NoteStore for JsonStore {
+    fn get(&self, key: NoteKey) -> Result<Option<TimeNote>, StoreError> {
+        self.notes.get(key)
+    }
+
+    fn put(&mut self, key: NoteKey, note: TimeNote) -> Result<(), StoreError> {
+        self.notes.put(key, note)?;
+        self.touched.insert(key);
+        self.dirty = true;
+        Ok(())
+    }
+
+    fn delete(&mut self, key: NoteKey) -> Result<Option<TimeNote>, StoreError> {
+        let removed = self.notes.delete(key)?;
+        if removed.is_some() {
+            self.touched.insert(key);
+            self.dirty = true;
+        }
+        Ok(removed)
+    }
+
+    fn range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Notes, StoreError> {
+        self.notes.range(from, to)
+    }
+
+    fn iter(&self) -> Result<Box<dyn Iterator<Item = (NoteKey, TimeNote)> + '_>, StoreError> {
+        self.notes.iter()
+    }
+
+    fn flush(&mut self) -> Result<(), StoreError> {
+        if self.dirty {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn verify(&self) -> Result<IntegrityReport, StoreError> {
+        let bytes = match fs::read(&self.path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound && self.notes.notes().is_empty() => {
+                return Ok(IntegrityReport::from_notes(&Notes::new(), integrity::digest(b"")));
+            }
+            result => result?,
+        };
+        let digest = integrity::digest(&bytes);
+        let mut problems = Vec::new();
+        if self.written_digest.as_ref().is_some_and(|written| *written != digest) {
+            problems.push("archive on disk differs from what was last written".to_string());
+        }
+
+        let entries: RawEntries = match serde_json::from_slice(&bytes) {
+            Ok(entries) => entries,
+            Err(e) => {
+                problems.push(format!("archive does not parse: {e}"));
+                return Ok(IntegrityReport { digest, problems, ..Default::default() });
+            }
+        };
+        let mut seen = HashSet::new();
+        let mut on_disk = Notes::new();
+        for (raw_key, value) in entries.0 {
+            let Some(key) = NoteKey::parse(&raw_key) else {
+                problems.push(format!("malformed key `{raw_key}`"));
+                continue;
+            };
+            if !seen.insert(key) {
+                problems.push(format!("{key}: duplicate key"));
+            }
+            match serde_json::from_value::<TimeNote>(value) {
+                Ok(note) => {
+                    on_disk.insert(key, note);
+                }
+                Err(e) => problems.push(format!("{key}: malformed note: {e}")),
+            }
+        }
+        // Unflushed edits legitimately differ from the file
+        if !self.dirty && on_disk != *self.notes.notes() {
+            problems.push("archive on disk does not match the open vault".to_string());
+        }
+
+        let mut report = IntegrityReport::from_notes(&on_disk, digest);
+        problems.append(&mut report.problems);
+        report.problems = problems;
+        Ok(report)
+    }
+
+    /// Writes the archive and takes a fresh snapshot of the secured state.
+    fn snapshot(&mut self) -> Result<(), StoreError> {
+        self.save()?;
+        backup::rotate(&self.path, self.backups)?;
+        Ok(())
+    }
+}
+
+/// Every top-level entry of the archive in file order, duplicates included,
+/// so verification sees exactly what was written rather than a deduplicated map.
+struct RawEntries(Vec<(String, serde_json::Value)>);
+
+impl<'de> Deserialize<'de> for RawEntries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = RawEntries;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map of date-hour keys to notes")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<RawEntries, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(RawEntries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+/// What could be rescued from a vault that failed to parse.
+#[derive(Clone, Debug)]
+pub struct Recovery {
+    pub path: PathBuf,
+    /// Untouched copy of the damaged file.
+    pub quarantine: PathBuf,
+    pub salvaged: Notes,
+    pub cause: String,
+}
+
+impl Recovery {
+    /// Quarantines the damaged vault at `path` and salvages what it can from
+    /// `data`, its contents as far as they could be read.
+    fn attempt(path: &Path, data: &str, cause: impl fmt::Display) -> StoreError {
+        let quarantine = match backup::quarantine(path) {
+            Ok(quarantine) => quarantine,
+            Err(e) => return e.into(),
+        };
+        let salvaged = migrate_notes(salvage(data));
+        StoreError::Corrupt(Box::new(Recovery { path: path.to_path_buf(), quarantine, salvaged, cause: cause.to_string() }))
+    }
+}
+
+/// Scans damaged JSON for `"key": { ...note... }` pairs that still parse on
+/// their own, skipping over whatever is broken between them.
+fn salvage(data: &str) -> HashMap<String, TimeNote> {
+    let mut recovered = HashMap::new();
+    let mut rest = data;
+    while let Some(start) = rest.find('"') {
+        let candidate = &rest[start..];
+        let mut keys = serde_json::Deserializer::from_str(candidate).into_iter::<String>();
+        let Some(Ok(key)) = keys.next() else {
+            rest = &candidate[1..];
+            continue;
+        };
+        let after_key = &candidate[keys.byte_offset()..];
+        if let Some(value) = after_key.trim_start().strip_prefix(':') {
+            let mut values = serde_json::Deserializer::from_str(value).into_iter::<TimeNote>();
+            if let Some(Ok(note)) = values.next() {
+                recovered.insert(key, note);
+                rest = &value[values.byte_offset()..];
+                continue;
+            }
+        }
+        rest = after_key;
+    }
+    recovered
+}
+
+fn legacy_backup_path(path: &Path) -> PathBuf {
+    path.with_extension("legacy.json")
+}
+
+/// Rewrites legacy keys to the canonical form. Should a legacy key and its
+/// canonical twin both exist, their contents are merged rather than dropped.
+fn migrate_notes(raw: HashMap<String, TimeNote>) -> Notes {
+    let mut notes = Notes::new();
+    // Canonical keys first so merged legacy text is appended after them
+    let (canonical, legacy): (Vec<_>, Vec<_>) = raw.into_iter().partition(|(k, _)| NoteKey::parse(k).is_some());
+    for (raw_key, note) in canonical.into_iter().chain(legacy) {
+        let Some(key) = NoteKey::parse(&raw_key).or_else(|| NoteKey::parse_legacy(&raw_key)) else {
+            eprintln!("chronos: skipping unrecognised note key `{raw_key}`");
+            continue;
+        };
+        notes
+            .entry(key)
+            .and_modify(|existing: &mut TimeNote| {
+                existing.content = format!("{}\n\n---\n\n{}", existing.content, note.content);
+                existing.is_locked |= note.is_locked;
+            })
+            .or_insert(note);
+    }
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> NoteKey {
+        NoteKey::parse(s).unwrap()
+    }
+
+    #[test]
+    fn salvages_and_quarantines_a_damaged_vault() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let mut store = JsonStore::open(&path).unwrap();
+        for (k, content) in [("2026-10-17-08", "dawn"), ("2026-10-17-09", "morning"), ("2026-10-17-21", "evening")] {
+            store.put(key(k), TimeNote { content: content.into(), is_locked: k.ends_with("21") }).unwrap();
+        }
+        store.flush().unwrap();
+        let intact = fs::read_to_string(&path).unwrap();
+
+        // Garble the middle note and cut the file off before it closes
+        let damaged = intact.replacen("\"morning\"", "\"morn", 1);
+        let damaged = &damaged[..damaged.rfind('}').unwrap()];
+        fs::write(&path, damaged).unwrap();
+
+        let Err(StoreError::Corrupt(recovery)) = JsonStore::open(&path) else { panic!("damaged vault opened") };
+        assert_eq!(recovery.path, path);
+        assert_eq!(recovery.salvaged.keys().copied().collect::<Vec<_>>(), [key("2026-10-17-08"), key("2026-10-17-21")]);
+        assert!(recovery.salvaged[&key("2026-10-17-21")].is_locked);
+        assert_eq!(fs::read_to_string(&recovery.quarantine).unwrap(), damaged);
+        assert_eq!(fs::read_to_string(&path).unwrap(), damaged, "the damaged vault is left as it was");
+    }
+
+    #[test]
+    fn salvages_a_vault_that_is_not_utf8() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        fs::write(&path, b"\xff").unwrap();
+        let Err(StoreError::Corrupt(recovery)) = JsonStore::open(&path) else { panic!("undecodable vault opened as empty") };
+        assert!(recovery.salvaged.is_empty());
+        assert_eq!(fs::read(&recovery.quarantine).unwrap(), b"\xff");
+        assert_eq!(fs::read(&path).unwrap(), b"\xff", "the damaged vault is left as it was");
+
+        // One bad byte inside a note loses only that note
+        let mut damaged = br#"{"2026-10-17-08": {"content": "dawn", "is_locked": true}, "2026-10-17-09": {"content": "mor"#.to_vec();
+        damaged.extend(b"\xffning\", \"is_locked\": false}}");
+        fs::write(&path, &damaged).unwrap();
+        let Err(StoreError::Corrupt(recovery)) = JsonStore::open(&path) else { panic!("undecodable vault opened") };
+        assert_eq!(recovery.salvaged.len(), 2);
+        assert_eq!(recovery.salvaged[&key("2026-10-17-08")].content, "dawn");
+        assert_eq!(recovery.salvaged[&key("2026-10-17-09")].content, "mor\u{fffd}ning");
+        assert_eq!(fs::read(&recovery.quarantine).unwrap(), damaged);
+    }
+
+    #[test]
+    fn unreadable_vault_is_an_error_not_an_empty_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        // A directory in the vault's place cannot be read as a file
+        assert!(matches!(JsonStore::open(dir.path()), Err(StoreError::Io(_))));
+        assert!(matches!(JsonStore::open(dir.path().join("missing.json")), Ok(store) if store.iter().unwrap().count() == 0));
+    }
+
+    #[test]
+    fn rewrites_legacy_keys_when_opened() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let legacy = r#"{"2026-10-17-9": {"content": "nine", "is_locked": true}, "2026-10-17-10": {"content": "ten", "is_locked": false}}"#;
+        fs::write(&path, legacy).unwrap();
+
+        let store = JsonStore::open(&path).unwrap();
+        assert_eq!(store.iter().unwrap().map(|(key, _)| key).collect::<Vec<_>>(), [key("2026-10-17-09"), key("2026-10-17-10")]);
+        assert_eq!(fs::read_to_string(legacy_backup_path(&path)).unwrap(), legacy);
+        let on_disk: HashMap<String, TimeNote> = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(on_disk.contains_key("2026-10-17-09") && !on_disk.contains_key("2026-10-17-9"));
+        let report = store.verify().unwrap();
+        assert!(report.passed(), "{:?}", report.problems);
+        assert_eq!(report.notes, 2);
+    }
+
+    #[test]
+    fn leaves_a_canonical_vault_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let canonical = r#"{"2026-10-17-09":{"content":"nine","is_locked":false}}"#;
+        fs::write(&path, canonical).unwrap();
+        JsonStore::open(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), canonical);
+        assert!(!legacy_backup_path(&path).exists());
+    }
+
+    #[test]
+    fn migration_merges_a_legacy_key_into_its_canonical_twin() {
+        let raw = HashMap::from([
+            ("2026-10-17-9".to_string(), TimeNote { content: "old".into(), is_locked: true }),
+            ("2026-10-17-09".to_string(), TimeNote { content: "new".into(), is_locked: false }),
+            ("2026-10-17-7".to_string(), TimeNote { content: "seven".into(), is_locked: false }),
+            ("2026-10-17-13".to_string(), TimeNote { content: "kept".into(), is_locked: false }),
+            ("2026-10-17-15pm".to_string(), TimeNote { content: "lost".into(), is_locked: false }),
+        ]);
+        let notes = migrate_notes(raw);
+        assert_eq!(notes.keys().copied().collect::<Vec<_>>(), [key("2026-10-17-07"), key("2026-10-17-09"), key("2026-10-17-13")]);
+        let nine = &notes[&key("2026-10-17-09")];
+        assert_eq!(nine.content, "new\n\n---\n\nold");
+        assert!(nine.is_locked, "a banked half keeps the merged hour banked");
+    }
+
+    #[test]
+    fn saving_keeps_what_another_process_wrote() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        let mut dial = JsonStore::open(&path).unwrap();
+        dial.put(key("2026-10-17-08"), TimeNote { content: "dawn".into(), is_locked: false }).unwrap();
+        dial.put(key("2026-10-17-09"), TimeNote { content: "morning".into(), is_locked: false }).unwrap();
+        dial.flush().unwrap();
+
+        // The command line adds one hour and rewrites another while the dial is open
+        let mut cli = JsonStore::open(&path).unwrap();
+        cli.put(key("2026-10-17-21"), TimeNote { content: "from cron".into(), is_locked: false }).unwrap();
+        cli.put(key("2026-10-17-09"), TimeNote { content: "over ssh".into(), is_locked: false }).unwrap();
+        cli.flush().unwrap();
+
+        dial.put(key("2026-10-17-08"), TimeNote { content: "dawn, revised".into(), is_locked: false }).unwrap();
+        dial.flush().unwrap();
+
+        let content = |store: &JsonStore, k| store.get(key(k)).unwrap().map(|note| note.content);
+        let reopened = JsonStore::open(&path).unwrap();
+        assert_eq!(content(&reopened, "2026-10-17-08").as_deref(), Some("dawn, revised"));
+        assert_eq!(content(&reopened, "2026-10-17-09").as_deref(), Some("over ssh"));
+        assert_eq!(content(&reopened, "2026-10-17-21").as_deref(), Some("from cron"));
+        assert_eq!(content(&dial, "2026-10-17-21").as_deref(), Some("from cron"), "the open store sees it too");
+        assert!(dial.verify().unwrap().passed());
+
+        // A deletion by the dial still wins over the copy on disk
+        dial.delete(key("2026-10-17-21")).unwrap();
+        dial.flush().unwrap();
+        assert_eq!(content(&JsonStore::open(&path).unwrap(), "2026-10-17-21"), None);
+    }
+
+    #[test]
+    fn salvage_skips_what_does_not_parse() {
+        let data = r#"{ "2026-10-17-08": {"content": "a", "is_locked": false}, "2026-10-17-09": {"content": 7}, "#;
+        let salvaged = salvage(data);
+        assert_eq!(salvaged.len(), 1);
+        assert_eq!(salvaged["2026-10-17-08"].content, "a");
+    }
+}
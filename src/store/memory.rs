@@ -0,0 +1,43 @@
+use super::{NoteStore, StoreError};
+use crate::note::{NoteKey, Notes, TimeNote};
+use chrono::NaiveDate;
+
+/// Volatile store for tests and throwaway sessions; nothing touches the disk.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    notes: Notes,
+}
+
+impl MemoryStore {
+    pub fn new(notes: Notes) -> Self {
+        Self { notes }
+    }
+
+    pub fn notes(&self) -> &Notes {
+        &self.notes
+    }
+}
+
+impl NoteStore for MemoryStore {
+    fn get(&self, key: NoteKey) -> Result<Option<TimeNote>, StoreError> {
+        Ok(self.notes.get(&key).cloned())
+    }
+
+    fn put(&mut self, key: NoteKey, note: TimeNote) -> Result<(), StoreError> {
+        self.notes.insert(key, note);
+        Ok(())
+    }
+
+    fn delete(&mut self, key: NoteKey) -> Result<Option<TimeNote>, StoreError> {
+        Ok(self.notes.remove(&key))
+    }
+
+    fn range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Notes, StoreError> {
+        let Some(span) = NoteKey::span(from, to) else { return Ok(Notes::new()) };
+        Ok(self.notes.range(span).map(|(k, n)| (*k, n.clone())).collect())
+    }
+
+    fn iter(&self) -> Result<Box<dyn Iterator<Item = (NoteKey, TimeNote)> + '_>, StoreError> {
+        Ok(Box::new(self.notes.iter().map(|(k, n)| (*k, n.clone()))))
+    }
+}
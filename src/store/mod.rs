@@ -0,0 +1,189 @@
+use crate::config::{Backend, Config, JSON_STORE_FILE};
+use crate::integrity::{self, IntegrityReport};
+use crate::note::{NoteKey, Notes, TimeNote};
+use chrono::NaiveDate;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+pub mod backup;
+mod json;
+mod memory;
+mod sqlite;
+
+pub use json::{JsonStore, Recovery};
+pub use memory::MemoryStore;
+pub use sqlite::SqliteStore;
+
+// --- Storage Backends ---
+
+/// A persistent home for the archive, addressed by date-hour key.
+pub trait NoteStore: Send {
+    fn get(&self, key: NoteKey) -> Result<Option<TimeNote>, StoreError>;
+
+    /// Stores a note. Backends may buffer writes until the next `flush`.
+    fn put(&mut self, key: NoteKey, note: TimeNote) -> Result<(), StoreError>;
+
+    /// Removes a note, returning it if it existed.
+    fn delete(&mut self, key: NoteKey) -> Result<Option<TimeNote>, StoreError>;
+
+    /// Every note whose date lies within `from..=to`, in chronological order.
+    /// A `None` bound leaves that end of the range open.
+    fn range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Notes, StoreError>;
+
+    /// The whole archive in chronological order.
+    fn iter(&self) -> Result<Box<dyn Iterator<Item = (NoteKey, TimeNote)> + '_>, StoreError>;
+
+    /// Forces any buffered state out to durable storage.
+    fn flush(&mut self) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    /// Flushes and, where the backend supports it, keeps a restorable copy.
+    fn snapshot(&mut self) -> Result<(), StoreError> {
+        self.flush()
+    }
+
+    /// Re-reads the archive from where it is kept and validates every note.
+    fn verify(&self) -> Result<IntegrityReport, StoreError> {
+        let notes: Notes = self.iter()?.collect();
+        let digest = integrity::digest(serde_json::to_string_pretty(&notes)?.as_bytes());
+        Ok(IntegrityReport::from_notes(&notes, digest))
+    }
+}
+
+#[derive(Debug)]
+pub enum StoreError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Sqlite(rusqlite::Error),
+    /// The JSON vault could not be parsed; it has been quarantined and
+    /// nothing will be written over it until the recovery is confirmed.
+    Corrupt(Box<Recovery>),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(e) => write!(f, "vault i/o failed: {e}"),
+            StoreError::Json(e) => write!(f, "vault encoding failed: {e}"),
+            StoreError::Sqlite(e) => write!(f, "vault database failed: {e}"),
+            StoreError::Corrupt(r) => write!(f, "vault {} is damaged: {}", r.path.display(), r.cause),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(e: io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(e: serde_json::Error) -> Self {
+        StoreError::Json(e)
+    }
+}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(e: rusqlite::Error) -> Self {
+        StoreError::Sqlite(e)
+    }
+}
+
+/// Opens the backend chosen in the configuration.
+pub fn open(config: &Config) -> Result<Box<dyn NoteStore>, StoreError> {
+    Ok(match config.backend {
+        Backend::Json => Box::new(JsonStore::open(config.store_path())?.with_backups(config.backups)),
+        Backend::Sqlite => {
+            let path = config.store_path();
+            // A brand-new database adopts the existing JSON vault instead of starting empty
+            let adopted = if !path.exists() && Path::new(JSON_STORE_FILE).exists() {
+                Some(JsonStore::open(JSON_STORE_FILE)?)
+            } else {
+                None
+            };
+            let mut db = SqliteStore::open(&path)?;
+            if let Some(json) = adopted {
+                db.import(json.iter()?)?;
+            }
+            Box::new(db)
+        }
+        Backend::Memory => Box::new(MemoryStore::default()),
+    })
+}
+
+/// Accepts a recovery: writes the salvaged notes over the damaged vault
+/// (the original stays in quarantine) and reopens the configured backend.
+pub fn restore(config: &Config, recovery: &Recovery) -> Result<Box<dyn NoteStore>, StoreError> {
+    let data = serde_json::to_string_pretty(&recovery.salvaged)?;
+    backup::write_atomic(&recovery.path, data.as_bytes())?;
+    open(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(s: &str) -> NoteKey {
+        NoteKey::parse(s).unwrap()
+    }
+
+    fn note(content: &str, is_locked: bool) -> TimeNote {
+        TimeNote { content: content.into(), is_locked }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    /// Put, get, range and delete behave the same on every backend.
+    fn exercise(store: &mut dyn NoteStore) {
+        store.put(key("2026-10-17-09"), note("morning", false)).unwrap();
+        store.put(key("2026-10-17-21"), note("evening", true)).unwrap();
+        store.put(key("2026-10-18-00"), note("midnight", false)).unwrap();
+        store.put(key("2026-10-17-09"), note("morning, revised", false)).unwrap();
+        store.flush().unwrap();
+
+        assert_eq!(store.get(key("2026-10-17-09")).unwrap(), Some(note("morning, revised", false)));
+        assert_eq!(store.get(key("2026-10-17-10")).unwrap(), None);
+
+        let range = |from: Option<&str>, to: Option<&str>| -> Vec<NoteKey> {
+            store.range(from.map(date), to.map(date)).unwrap().into_keys().collect()
+        };
+        assert_eq!(range(Some("2026-10-17"), Some("2026-10-17")), [key("2026-10-17-09"), key("2026-10-17-21")]);
+        assert_eq!(range(Some("2026-10-18"), None), [key("2026-10-18-00")]);
+        assert_eq!(range(None, Some("2026-10-17")), [key("2026-10-17-09"), key("2026-10-17-21")]);
+        assert_eq!(range(None, None).len(), 3);
+        assert!(range(Some("2026-10-18"), Some("2026-10-17")).is_empty());
+
+        assert_eq!(store.delete(key("2026-10-17-21")).unwrap(), Some(note("evening", true)));
+        assert_eq!(store.delete(key("2026-10-17-21")).unwrap(), None);
+        store.flush().unwrap();
+        let all: Vec<NoteKey> = store.iter().unwrap().map(|(key, _)| key).collect();
+        assert_eq!(all, [key("2026-10-17-09"), key("2026-10-18-00")]);
+    }
+
+    #[test]
+    fn memory_store() {
+        exercise(&mut MemoryStore::default());
+    }
+
+    #[test]
+    fn json_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.json");
+        exercise(&mut JsonStore::open(&path).unwrap());
+        assert_eq!(JsonStore::open(&path).unwrap().iter().unwrap().count(), 2);
+    }
+
+    #[test]
+    fn sqlite_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.db");
+        exercise(&mut SqliteStore::open(&path).unwrap());
+        assert_eq!(SqliteStore::open(&path).unwrap().iter().unwrap().count(), 2);
+    }
+}
@@ -0,0 +1,156 @@
+use super::{NoteStore, StoreError};
+use crate::integrity::{self, IntegrityReport};
+use crate::note::{NoteKey, Notes, TimeNote};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::Path;
+
+/// Embedded SQLite archive, indexed by date so multi-year vaults stay quick to query.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS notes (
+        date      TEXT    NOT NULL,
+        hour      INTEGER NOT NULL CHECK (hour BETWEEN 0 AND 23),
+        content   TEXT    NOT NULL,
+        is_locked INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (date, hour)
+    ) WITHOUT ROWID;
+";
+
+const UPSERT: &str = "
+    INSERT INTO notes (date, hour, content, is_locked) VALUES (?1, ?2, ?3, ?4)
+    ON CONFLICT (date, hour) DO UPDATE SET content = excluded.content, is_locked = excluded.is_locked
+";
+
+impl SqliteStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Bulk-inserts notes in a single transaction, replacing any existing hours.
+    pub fn import(&mut self, notes: impl Iterator<Item = (NoteKey, TimeNote)>) -> Result<(), StoreError> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(UPSERT)?;
+            for (key, note) in notes {
+                stmt.execute(params![date_param(key.date), key.hour, note.content, note.is_locked])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn query(&self, sql: &str, params: impl rusqlite::Params) -> Result<Notes, StoreError> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, row_to_note)?;
+        let mut notes = Notes::new();
+        for row in rows {
+            let (key, note) = row?;
+            notes.insert(key, note);
+        }
+        Ok(notes)
+    }
+}
+
+fn date_param(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn row_to_note(row: &Row<'_>) -> rusqlite::Result<(NoteKey, TimeNote)> {
+    let date: String = row.get(0)?;
+    let hour: u32 = row.get(1)?;
+    let key = NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| NoteKey::new(date, hour))
+        .ok_or_else(|| rusqlite::Error::InvalidColumnType(0, "date".into(), rusqlite::types::Type::Text))?;
+    Ok((key, TimeNote { content: row.get(2)?, is_locked: row.get(3)? }))
+}
+
+impl NoteStore for SqliteStore {
+    fn get(&self, key: NoteKey) -> Result<Option<TimeNote>, StoreError> {
+        Ok(self
+            .conn
+            .query_row(
+                "SELECT date, hour, content, is_locked FROM notes WHERE date = ?1 AND hour = ?2",
+                params![date_param(key.date), key.hour],
+                row_to_note,
+            )
+            .optional()?
+            .map(|(_, note)| note))
+    }
+
+    fn put(&mut self, key: NoteKey, note: TimeNote) -> Result<(), StoreError> {
+        self.conn.execute(
+            UPSERT,
+            params![date_param(key.date), key.hour, note.content, note.is_locked],
+        )?;
+        Ok(())
+    }
+
+    fn delete(&mut self, key: NoteKey) -> Result<Option<TimeNote>, StoreError> {
+        let existing = self.get(key)?;
+        if existing.is_some() {
+            self.conn.execute(
+                "DELETE FROM notes WHERE date = ?1 AND hour = ?2",
+                params![date_param(key.date), key.hour],
+            )?;
+        }
+        Ok(existing)
+    }
+
+    fn range(&self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<Notes, StoreError> {
+        // Dates compare as text, so an open end must drop its condition rather
+        // than stand in an extreme date like `+262143-12-31`
+        self.query(
+            "SELECT date, hour, content, is_locked FROM notes WHERE (?1 IS NULL OR date >= ?1) AND (?2 IS NULL OR date <= ?2)",
+            params![from.map(date_param), to.map(date_param)],
+        )
+    }
+
+    fn iter(&self) -> Result<Box<dyn Iterator<Item = (NoteKey, TimeNote)> + '_>, StoreError> {
+        let notes = self.query("SELECT date, hour, content, is_locked FROM notes", [])?;
+        Ok(Box::new(notes.into_iter()))
+    }
+
+    /// Runs SQLite's own page-level check, then re-reads every raw row so
+    /// malformed dates or flags are reported instead of failing the read.
+    fn verify(&self) -> Result<IntegrityReport, StoreError> {
+        let mut problems = Vec::new();
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        for line in stmt.query_map([], |row| row.get::<_, String>(0))? {
+            let line = line?;
+            if line != "ok" {
+                problems.push(format!("database: {line}"));
+            }
+        }
+
+        let mut notes = Notes::new();
+        let mut stmt = self.conn.prepare("SELECT date, hour, content, is_locked FROM notes ORDER BY date, hour")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let date: String = row.get(0)?;
+            let hour: i64 = row.get(1)?;
+            let raw_key = format!("{date}-{hour:02}");
+            let Some(key) = NoteKey::parse(&raw_key) else {
+                problems.push(format!("malformed key `{raw_key}`"));
+                continue;
+            };
+            let is_locked: i64 = row.get(3)?;
+            if !matches!(is_locked, 0 | 1) {
+                problems.push(format!("{key}: lock flag is {is_locked}"));
+            }
+            notes.insert(key, TimeNote { content: row.get(2)?, is_locked: is_locked != 0 });
+        }
+
+        let digest = integrity::digest(serde_json::to_string_pretty(&notes)?.as_bytes());
+        let mut report = IntegrityReport::from_notes(&notes, digest);
+        problems.append(&mut report.problems);
+        report.problems = problems;
+        Ok(report)
+    }
+}